@@ -0,0 +1,180 @@
+// Game collections packed in .zip or .tar.gz archives. An entry is addressed
+// as the archive path joined with the entry name, e.g.
+// "collections/2016.zip/round1/game3.sgf", so playlists and loaders can keep
+// passing plain paths around and read entries without extracting them.
+
+use crate::import::is_game_file;
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveKind {
+    Zip,
+    TarGz,
+}
+
+fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else {
+        None
+    }
+}
+
+pub fn is_archive(path: &Path) -> bool {
+    archive_kind(path).is_some()
+}
+
+// Paths of the game entries inside `archive`, in archive order
+pub fn list_entries(archive: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    ArchiveCache::default().list_entries(archive)
+}
+
+// Split an entry path into its archive and the entry name inside it
+pub fn split_entry_path(path: &Path) -> Option<(&Path, String)> {
+    let archive = path
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| is_archive(ancestor) && ancestor.is_file())?;
+    let entry = path.strip_prefix(archive).ok()?;
+    Some((archive, entry_name(entry)))
+}
+
+// Entry names use '/' separators with any leading "./" dropped
+fn entry_name(path: &Path) -> String {
+    let parts: Vec<String> = path
+        .components()
+        .filter(|part| matches!(part, Component::Normal(_)))
+        .map(|part| part.as_os_str().to_string_lossy().to_string())
+        .collect();
+    parts.join("/")
+}
+
+// Read one game entry's bytes out of the archive
+pub fn read_entry(archive: &Path, name: &str) -> Result<Vec<u8>, io::Error> {
+    ArchiveCache::default().read_entry(archive, name)
+}
+
+// The game entries of a .tar.gz archive. A tar file can only be read from
+// the start, so the games are decompressed together once and kept in memory
+struct TarContents {
+    stamp: (Option<SystemTime>, u64), // Modified time and length when read
+    names: Vec<String>,               // Every file entry, in archive order
+    games: HashMap<String, Vec<u8>>,  // Game files only
+}
+
+// The .tar.gz archives read so far, by path. Its owner (e.g. a playlist
+// listing a bundle and loading its games one after another) then
+// decompresses each archive once, and the games go when the owner does. An
+// archive that changed since it was read is read again
+#[derive(Default)]
+pub struct ArchiveCache {
+    tars: HashMap<PathBuf, TarContents>,
+}
+
+impl ArchiveCache {
+    pub fn list_entries(&mut self, archive: &Path) -> Result<Vec<PathBuf>, io::Error> {
+        let names = match archive_kind(archive) {
+            Some(ArchiveKind::Zip) => open_zip(archive)?
+                .file_names()
+                .map(|name| name.to_string())
+                .collect(),
+            Some(ArchiveKind::TarGz) => self.tar_contents(archive)?.names.clone(),
+            None => return Err(not_an_archive(archive)),
+        };
+
+        Ok(names
+            .into_iter()
+            .filter(|name| !name.ends_with('/') && is_game_file(Path::new(name)))
+            .map(|name| archive.join(name))
+            .collect())
+    }
+
+    pub fn read_entry(&mut self, archive: &Path, name: &str) -> Result<Vec<u8>, io::Error> {
+        match archive_kind(archive) {
+            Some(ArchiveKind::Zip) => {
+                let mut zip = open_zip(archive)?;
+                let mut entry = zip.by_name(name).map_err(zip_error)?;
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data)?;
+                Ok(data)
+            }
+            Some(ArchiveKind::TarGz) => self
+                .tar_contents(archive)?
+                .games
+                .get(name)
+                .cloned()
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("{} not found in {}", name, archive.display()),
+                    )
+                }),
+            None => Err(not_an_archive(archive)),
+        }
+    }
+
+    fn tar_contents(&mut self, archive: &Path) -> Result<&TarContents, io::Error> {
+        let metadata = fs::metadata(archive)?;
+        let stamp = (metadata.modified().ok(), metadata.len());
+        let current = self
+            .tars
+            .get(archive)
+            .is_some_and(|contents| contents.stamp == stamp);
+        if !current {
+            self.tars.remove(archive);
+            let contents = read_tar(archive, stamp)?;
+            self.tars.insert(archive.to_path_buf(), contents);
+        }
+        Ok(&self.tars[archive])
+    }
+}
+
+fn read_tar(archive: &Path, stamp: (Option<SystemTime>, u64)) -> Result<TarContents, io::Error> {
+    let mut contents = TarContents {
+        stamp,
+        names: Vec::new(),
+        games: HashMap::new(),
+    };
+    let mut tar = tar::Archive::new(GzDecoder::new(File::open(archive)?));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry_name(&entry.path()?);
+        if is_game_file(Path::new(&name)) {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            contents.games.insert(name.clone(), data);
+        }
+        contents.names.push(name);
+    }
+    Ok(contents)
+}
+
+fn open_zip(archive: &Path) -> Result<zip::ZipArchive<File>, io::Error> {
+    zip::ZipArchive::new(File::open(archive)?).map_err(zip_error)
+}
+
+fn zip_error(e: zip::result::ZipError) -> io::Error {
+    match e {
+        zip::result::ZipError::Io(e) => e,
+        zip::result::ZipError::FileNotFound => io::Error::new(io::ErrorKind::NotFound, e),
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+fn not_an_archive(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Not a .zip or .tar.gz archive: {}", path.display()),
+    )
+}
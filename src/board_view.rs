@@ -1,6 +1,70 @@
 use crate::game::Board;
 use crate::parser::Color;
 
+// Stone emoji used by the board renderers - each naturally takes 2 char widths
+pub const BLACK_STONE: &str = "⚫";
+pub const WHITE_STONE: &str = "⚪";
+
+// Which part of the grid an empty point sits on, in view coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intersection {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Center,
+}
+
+impl Intersection {
+    pub fn at(row: u8, col: u8, size: u8) -> Self {
+        let last = size - 1;
+        match (row, col) {
+            (0, 0) => Intersection::TopLeft,
+            (0, c) if c == last => Intersection::TopRight,
+            (r, 0) if r == last => Intersection::BottomLeft,
+            (r, c) if r == last && c == last => Intersection::BottomRight,
+            (0, _) => Intersection::Top,
+            (r, _) if r == last => Intersection::Bottom,
+            (_, 0) => Intersection::Left,
+            (_, c) if c == last => Intersection::Right,
+            _ => Intersection::Center,
+        }
+    }
+
+    // 2 characters wide to match the stone emoji width
+    pub fn regular_glyph(&self) -> &'static str {
+        match self {
+            Intersection::TopLeft => "┌─",
+            Intersection::TopRight => "─┐",
+            Intersection::BottomLeft => "└─",
+            Intersection::BottomRight => "─┘",
+            Intersection::Top => "─┬",
+            Intersection::Bottom => "─┴",
+            Intersection::Left => "├─",
+            Intersection::Right => "─┤",
+            Intersection::Center => "─┼",
+        }
+    }
+
+    pub fn compact_glyph(&self) -> &'static str {
+        match self {
+            Intersection::TopLeft => "┌",
+            Intersection::TopRight => "┐",
+            Intersection::BottomLeft => "└",
+            Intersection::BottomRight => "┘",
+            Intersection::Top => "┬",
+            Intersection::Bottom => "┴",
+            Intersection::Left => "├",
+            Intersection::Right => "┤",
+            Intersection::Center => "┼",
+        }
+    }
+}
+
 pub struct BoardView<'a> {
     board: &'a Board,
     rotation: u8,
@@ -19,14 +83,31 @@ impl<'a> BoardView<'a> {
     }
 
     pub fn get(&self, view_row: u8, view_col: u8) -> Option<Color> {
+        let (board_row, board_col) = self.to_board(view_row, view_col);
+        self.board.get(board_row, board_col)
+    }
+
+    // Map a position in the rotated view back to the underlying board position
+    pub fn to_board(&self, view_row: u8, view_col: u8) -> (u8, u8) {
         let size = self.board.size - 1;
-        let (board_row, board_col) = match self.rotation {
+        match self.rotation {
             0 => (view_row, view_col),
             1 => (view_col, size - view_row),
             2 => (size - view_row, size - view_col),
             3 => (size - view_col, view_row),
             _ => (view_row, view_col),
-        };
-        self.board.get(board_row, board_col)
+        }
+    }
+
+    // Inverse of to_board: where a board position appears in the rotated view
+    pub fn to_view(&self, board_row: u8, board_col: u8) -> (u8, u8) {
+        let size = self.board.size - 1;
+        match self.rotation {
+            0 => (board_row, board_col),
+            1 => (size - board_col, board_row),
+            2 => (size - board_row, size - board_col),
+            3 => (board_col, size - board_row),
+            _ => (board_row, board_col),
+        }
     }
 }
@@ -0,0 +1,106 @@
+// Bookmarked positions, kept in a plain tab-separated file under the user's
+// data directory so other tools can read and add to it:
+//
+//   <game path>  <move number>  <label>
+//
+// A position is a move on the game's main line (the line the viewer plays).
+// Paths are stored absolute; the label may be empty.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# smartgameviewer bookmarks";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub path: PathBuf,
+    pub move_number: usize,
+    pub label: Option<String>,
+}
+
+impl Bookmark {
+    pub fn new(path: &Path, move_number: usize, label: Option<&str>) -> Self {
+        Bookmark {
+            path: std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+            move_number,
+            label: label
+                .map(|label| label.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|label| !label.is_empty()),
+        }
+    }
+}
+
+// Bookmarks in the order they were added
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookmarkStore {
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    // <data dir>/smartgameviewer/bookmarks, e.g. ~/.local/share on Linux
+    pub fn default_path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("smartgameviewer").join("bookmarks"))
+    }
+
+    // A missing file is an empty store; lines that don't parse are skipped
+    pub fn load(path: &Path) -> Result<Self, io::Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut store = Self::default();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let mut fields = line.splitn(3, '\t');
+            let (Some(game), Some(number)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            if let Ok(move_number) = number.trim().parse() {
+                store.add(Bookmark::new(Path::new(game), move_number, fields.next()));
+            }
+        }
+        Ok(store)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), io::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut text = format!("{}\n", HEADER);
+        for bookmark in &self.bookmarks {
+            text.push_str(&format!(
+                "{}\t{}\t{}\n",
+                bookmark.path.display(),
+                bookmark.move_number,
+                bookmark.label.as_deref().unwrap_or("")
+            ));
+        }
+        fs::write(path, text)
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    // Bookmarking a position again only updates its label
+    pub fn add(&mut self, bookmark: Bookmark) {
+        match self.find(&bookmark.path, bookmark.move_number) {
+            Some(index) => self.bookmarks[index].label = bookmark.label,
+            None => self.bookmarks.push(bookmark),
+        }
+    }
+
+    pub fn remove(&mut self, path: &Path, move_number: usize) -> Option<Bookmark> {
+        let index = self.find(path, move_number)?;
+        Some(self.bookmarks.remove(index))
+    }
+
+    pub fn find(&self, path: &Path, move_number: usize) -> Option<usize> {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        self.bookmarks
+            .iter()
+            .position(|bookmark| bookmark.path == path && bookmark.move_number == move_number)
+    }
+}
@@ -0,0 +1,491 @@
+use crate::export::animation::{AnimationFormat, AnimationOptions};
+use crate::export::kifu::KifuOptions;
+use crate::export::text::TextStyle;
+use crate::export::{self, DiagramOptions, Theme};
+use crate::game;
+use crate::pattern::{point_name, Pattern, PatternSearch, Region};
+use crate::playlist::{
+    self, GameQuery, PlaylistManager, RepeatMode, ScanOptions, SortKey, STDIN_ARG,
+};
+use crate::position::PositionIndex;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+pub const USAGE: &str = "\
+Usage:
+  smartgameviewer [FILE|DIR|ARCHIVE|PLAYLIST] [VIEW OPTIONS]
+                                          View a game or every game in a folder, archive or
+                                          .sgfplaylist file
+  smartgameviewer -                       View games read from stdin (also when piped with no FILE)
+  smartgameviewer svg FILE [OPTIONS]      Export a position as an SVG diagram
+  smartgameviewer png FILE [OPTIONS]      Export a position as a PNG image
+  smartgameviewer animate FILE [OPTIONS]  Export a replay as an animated GIF or APNG
+  smartgameviewer dump FILE [OPTIONS]     Print a position as text and exit
+  smartgameviewer kifu FILE [OPTIONS]     Export a printable HTML game record
+  smartgameviewer find FILE LIBRARY [-m N]
+                                          List the games in a folder, archive or playlist
+                                          that reached the position after move N of FILE
+                                          (default: final position), in any rotation,
+                                          reflection or with colours swapped
+  smartgameviewer pattern FILE LIBRARY --region REGION [-m N]
+                                          Look for a region of that position in the library
+                                          (also rotated, reflected or colour-swapped) and
+                                          count the moves played next in the region.
+                                          REGION is two corners (\"A19:G13\") or top-left,
+                                          top-right, bottom-left or bottom-right (7x7)
+
+View options:
+      --depth N      Descend at most N folder levels (default: unlimited, 0 = no subfolders)
+      --include GLOB Only play files matching GLOB (repeatable), e.g. \"*.sgf\" or \"2023/**\"
+      --exclude GLOB Skip files and folders matching GLOB (repeatable)
+      --no-follow-symlinks
+                     Don't descend into symlinked folders
+      --player NAME  Only games where black or white matches NAME
+      --event TEXT   Only games whose event (EV) contains TEXT
+      --date DATE    Only games from DATE (\"2016\", \"2016-03\") or a range (\"2015..2017\")
+      --result TEXT  Only games whose result contains TEXT (\"W+\", \"+R\" for resignations)
+      --sort KEY     Order by name (default), date, event or player
+      --shuffle      Play in random order (the seed is shown in the help overlay)
+      --seed N       Shuffle with seed N to repeat an earlier order
+      --repeat MODE  After a game: all (next game, wrap around; default),
+                     one (replay it) or off (next game, stop after the last)
+      --watch        Check the folder every few seconds: new games join the
+                     rotation, deleted ones leave it
+      --follow       Live mode: re-read the game file as it grows and stay on the
+                     latest move
+      --no-resume    Don't offer to continue where the last session stopped, and
+                     don't remember this one
+      --save-playlist FILE
+                     Write the games, filtered and in order, to a .sgfplaylist
+                     file and exit
+
+Export options:
+  -m, --move N       Position after move N (default: final position)
+  -o, --output FILE  Write to FILE instead of stdout
+  -r, --rotate DEG   Rotate the board by 0, 90, 180 or 270 degrees
+      --numbers      Number stones with the move that placed them
+      --no-coords    Leave out coordinate labels
+  -t, --theme NAME   Color theme: wood (default), light or dark
+  -s, --size PX      Image width and height in pixels, 64 to 8192 (png 800, animate 480)
+
+Animate options:
+      --from N       First frame shows the position after move N (default 0)
+      --to N         Last frame shows the position after move N (default: end)
+  -d, --delay MS     Delay between frames in milliseconds (default 500)
+  -f, --format FMT   gif or apng (default: from the output extension, else gif)
+
+Dump options:
+      --ascii        Plain ASCII (default when stdout is not a terminal)
+      --ansi         Colored box-drawing board (default on a terminal)
+
+Kifu options:
+  -e, --every N      Moves per diagram (default 50)";
+
+pub enum Command {
+    View(ViewArgs),
+    Svg(ExportArgs),
+    Png(ExportArgs),
+    Animate(ExportArgs),
+    Dump(ExportArgs),
+    Kifu(ExportArgs),
+    Find(FindArgs),
+    Pattern(FindArgs),
+}
+
+#[derive(Default)]
+pub struct ViewArgs {
+    pub path: Option<String>,
+    pub scan: ScanOptions,
+    pub query: GameQuery,
+    pub sort: Option<SortKey>,
+    pub shuffle: Option<u64>, // Seed
+    pub repeat: RepeatMode,
+    pub save_playlist: Option<String>,
+    pub watch: bool,
+    pub follow: bool,
+    pub no_resume: bool,
+}
+
+pub struct ExportArgs {
+    pub path: String,
+    pub move_number: Option<usize>,
+    pub output: Option<String>,
+    pub options: DiagramOptions,
+    pub pixel_size: Option<u32>,
+    pub first_move: usize,
+    pub last_move: Option<usize>,
+    pub frame_delay_ms: u32,
+    pub format: Option<AnimationFormat>,
+    pub text_style: Option<TextStyle>,
+    pub moves_per_diagram: Option<usize>,
+}
+
+pub struct FindArgs {
+    pub path: String,
+    pub move_number: Option<usize>,
+    pub library: String,
+    pub region: Option<String>, // Pattern search only
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|s| s.as_str()) {
+        Some("svg") => parse_export_args(&args[1..]).map(Command::Svg),
+        Some("png") => parse_export_args(&args[1..]).map(Command::Png),
+        Some("animate") => parse_export_args(&args[1..]).map(Command::Animate),
+        Some("dump") => parse_export_args(&args[1..]).map(Command::Dump),
+        Some("kifu") => parse_export_args(&args[1..]).map(Command::Kifu),
+        Some("find") => parse_find_args(&args[1..]).map(Command::Find),
+        Some("pattern") => parse_find_args(&args[1..])
+            .and_then(|find| match find.region {
+                Some(_) => Ok(find),
+                None => Err(format!("Missing --region\n\n{}", USAGE)),
+            })
+            .map(Command::Pattern),
+        Some("-h") | Some("--help") => Err(USAGE.to_string()),
+        _ => parse_view_args(args),
+    }
+}
+
+fn parse_view_args(args: &[String]) -> Result<Command, String> {
+    let mut view = ViewArgs::default();
+    let scan = &mut view.scan;
+    let query = &mut view.query;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--depth" => scan.max_depth = Some(parse_number(arg, iter.next())?),
+            "--include" => scan.include.push(flag_value(arg, iter.next())?.to_string()),
+            "--exclude" => scan.exclude.push(flag_value(arg, iter.next())?.to_string()),
+            "--no-follow-symlinks" => scan.follow_symlinks = false,
+            "--player" => query.player = Some(flag_value(arg, iter.next())?.to_string()),
+            "--event" => query.event = Some(flag_value(arg, iter.next())?.to_string()),
+            "--date" => query.date = Some(flag_value(arg, iter.next())?.to_string()),
+            "--result" => query.result = Some(flag_value(arg, iter.next())?.to_string()),
+            "--sort" => {
+                let value = flag_value(arg, iter.next())?;
+                view.sort = Some(
+                    SortKey::from_name(value)
+                        .ok_or_else(|| format!("Unknown sort key: {}", value))?,
+                );
+            }
+            "--shuffle" => view.shuffle = view.shuffle.or_else(|| Some(playlist::random_seed())),
+            "--seed" => view.shuffle = Some(parse_number(arg, iter.next())?),
+            "--repeat" => {
+                let value = flag_value(arg, iter.next())?;
+                view.repeat = RepeatMode::from_name(value)
+                    .ok_or_else(|| format!("Unknown repeat mode: {}", value))?;
+            }
+            "--save-playlist" => {
+                view.save_playlist = Some(flag_value(arg, iter.next())?.to_string())
+            }
+            "--watch" => view.watch = true,
+            "--follow" => view.follow = true,
+            "--no-resume" => view.no_resume = true,
+            flag if flag.starts_with('-') && flag != STDIN_ARG => {
+                return Err(format!("Unknown option: {}\n\n{}", flag, USAGE))
+            }
+            _ if view.path.is_none() => view.path = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument: {}\n\n{}", arg, USAGE)),
+        }
+    }
+
+    view.scan.validate()?;
+    Ok(Command::View(view))
+}
+
+fn parse_export_args(args: &[String]) -> Result<ExportArgs, String> {
+    let mut path = None;
+    let mut move_number = None;
+    let mut output = None;
+    let mut options = DiagramOptions::default();
+    let mut pixel_size = None;
+    let mut first_move = 0;
+    let mut last_move = None;
+    let mut frame_delay_ms = 500;
+    let mut format = None;
+    let mut text_style = None;
+    let mut moves_per_diagram = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-m" | "--move" => {
+                move_number = Some(parse_number(arg, iter.next())?);
+            }
+            "-o" | "--output" => output = Some(flag_value(arg, iter.next())?.to_string()),
+            "-r" | "--rotate" => {
+                let value = flag_value(arg, iter.next())?;
+                options.rotation = match value {
+                    "0" => 0,
+                    "90" => 1,
+                    "180" => 2,
+                    "270" => 3,
+                    _ => return Err(format!("Rotation must be 0, 90, 180 or 270: {}", value)),
+                };
+            }
+            "-t" | "--theme" => {
+                let value = flag_value(arg, iter.next())?;
+                options.theme =
+                    Theme::from_name(value).ok_or_else(|| format!("Unknown theme: {}", value))?;
+            }
+            "-s" | "--size" => {
+                let value = flag_value(arg, iter.next())?;
+                let px: u32 = value
+                    .parse()
+                    .ok()
+                    .filter(|px| (64..=8192).contains(px))
+                    .ok_or_else(|| format!("Size must be 64 to 8192 pixels: {}", value))?;
+                pixel_size = Some(px);
+            }
+            "--from" => first_move = parse_number(arg, iter.next())?,
+            "--to" => last_move = Some(parse_number(arg, iter.next())?),
+            "-d" | "--delay" => frame_delay_ms = parse_number(arg, iter.next())?,
+            "-f" | "--format" => {
+                let value = flag_value(arg, iter.next())?;
+                format = Some(
+                    AnimationFormat::from_name(value)
+                        .ok_or_else(|| format!("Unknown animation format: {}", value))?,
+                );
+            }
+            "-e" | "--every" => {
+                let value = flag_value(arg, iter.next())?;
+                let every =
+                    value.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                        format!("Moves per diagram must be at least 1: {}", value)
+                    })?;
+                moves_per_diagram = Some(every);
+            }
+            "--ascii" => text_style = Some(TextStyle::Ascii),
+            "--ansi" => text_style = Some(TextStyle::Ansi),
+            "--numbers" => options.move_numbers = true,
+            "--no-coords" => options.coordinates = false,
+            flag if flag.starts_with('-') => {
+                return Err(format!("Unknown option: {}\n\n{}", flag, USAGE))
+            }
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument: {}\n\n{}", arg, USAGE)),
+        }
+    }
+
+    Ok(ExportArgs {
+        path: path.ok_or_else(|| format!("Missing SGF file\n\n{}", USAGE))?,
+        move_number,
+        output,
+        options,
+        pixel_size,
+        first_move,
+        last_move,
+        frame_delay_ms,
+        format,
+        text_style,
+        moves_per_diagram,
+    })
+}
+
+fn parse_find_args(args: &[String]) -> Result<FindArgs, String> {
+    let mut paths = Vec::new();
+    let mut move_number = None;
+    let mut region = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-m" | "--move" => move_number = Some(parse_number(arg, iter.next())?),
+            "--region" => region = Some(flag_value(arg, iter.next())?.to_string()),
+            flag if flag.starts_with('-') => {
+                return Err(format!("Unknown option: {}\n\n{}", flag, USAGE))
+            }
+            _ if paths.len() < 2 => paths.push(arg.clone()),
+            _ => return Err(format!("Unexpected argument: {}\n\n{}", arg, USAGE)),
+        }
+    }
+
+    let mut paths = paths.into_iter();
+    Ok(FindArgs {
+        path: paths
+            .next()
+            .ok_or_else(|| format!("Missing SGF file\n\n{}", USAGE))?,
+        move_number,
+        library: paths
+            .next()
+            .ok_or_else(|| format!("Missing library to search\n\n{}", USAGE))?,
+        region,
+    })
+}
+
+fn flag_value<'a>(flag: &str, value: Option<&'a String>) -> Result<&'a str, String> {
+    value
+        .map(|v| v.as_str())
+        .ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = flag_value(flag, value)?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid number for {}: {}", flag, value))
+}
+
+pub fn run_svg(args: &ExportArgs) -> Result<(), io::Error> {
+    let game = export::load_position(Path::new(&args.path), args.move_number)?;
+    let svg = export::svg::render_svg(&game, &args.options);
+    write_output(args.output.as_deref(), svg.as_bytes())
+}
+
+pub fn run_png(args: &ExportArgs) -> Result<(), io::Error> {
+    let game = export::load_position(Path::new(&args.path), args.move_number)?;
+    let png = export::png::render_png(&game, &args.options, args.pixel_size.unwrap_or(800))?;
+    write_output(args.output.as_deref(), &png)
+}
+
+pub fn run_animate(args: &ExportArgs) -> Result<(), io::Error> {
+    let mut game = game::load_game_from_path(Path::new(&args.path))?;
+    let defaults = AnimationOptions::default();
+    let options = AnimationOptions {
+        diagram: args.options.clone(),
+        pixel_size: args.pixel_size.unwrap_or(defaults.pixel_size),
+        frame_delay_ms: args.frame_delay_ms,
+        first_move: args.first_move,
+        last_move: args.last_move,
+    };
+
+    // Pick the format from the output extension unless given explicitly
+    let format = args.format.unwrap_or_else(|| {
+        let extension = args
+            .output
+            .as_deref()
+            .and_then(|output| Path::new(output).extension())
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("gif");
+        AnimationFormat::from_name(extension).unwrap_or(AnimationFormat::Gif)
+    });
+
+    let data = export::animation::render_animation(&mut game, &options, format)?;
+    write_output(args.output.as_deref(), &data)
+}
+
+pub fn run_dump(args: &ExportArgs) -> Result<(), io::Error> {
+    let game = export::load_position(Path::new(&args.path), args.move_number)?;
+
+    // Colors only make sense when a terminal will interpret them
+    let style = args.text_style.unwrap_or_else(|| {
+        if args.output.is_none() && io::stdout().is_terminal() {
+            TextStyle::Ansi
+        } else {
+            TextStyle::Ascii
+        }
+    });
+
+    let text = export::text::render_text(&game, &args.options, style);
+    write_output(args.output.as_deref(), text.as_bytes())
+}
+
+pub fn run_kifu(args: &ExportArgs) -> Result<(), io::Error> {
+    let mut game = game::load_game_from_path(Path::new(&args.path))?;
+    let defaults = KifuOptions::default();
+    let options = KifuOptions {
+        diagram: args.options.clone(),
+        moves_per_diagram: args.moves_per_diagram.unwrap_or(defaults.moves_per_diagram),
+    };
+
+    let html = export::kifu::render_kifu_html(&mut game, &options);
+    write_output(args.output.as_deref(), html.as_bytes())
+}
+
+// Print one line per game that reached the position: path, move number, and
+// a note when it was reached rotated, reflected or with colours swapped
+pub fn run_find(args: &FindArgs) -> Result<(), io::Error> {
+    let game = export::load_position(Path::new(&args.path), args.move_number)?;
+    let library = PlaylistManager::new(Some(&args.library))?;
+    let index = PositionIndex::build(&library);
+    for path in index.unreadable() {
+        eprintln!("Skipped unreadable game: {}", path.display());
+    }
+
+    let hits = index.find(game.position_hash());
+    let mut out = String::new();
+    for hit in &hits {
+        out.push_str(&format!(
+            "{}\tmove {}{}\n",
+            hit.path.display(),
+            hit.move_number,
+            if hit.exact { "" } else { "\t(transformed)" }
+        ));
+    }
+    eprintln!(
+        "Position found in {} of {} games",
+        hits.len(),
+        index.games().len()
+    );
+    io::stdout().write_all(out.as_bytes())
+}
+
+// Print the continuations with how often each was played, then one line per
+// appearance: path, move number and the continuation played there
+pub fn run_pattern(args: &FindArgs) -> Result<(), io::Error> {
+    let game = export::load_position(Path::new(&args.path), args.move_number)?;
+    let size = game.board.size;
+    let region = Region::parse(args.region.as_deref().unwrap_or_default(), size)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let pattern = Pattern::from_board(&game.board, region);
+    if pattern.stones() == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("No stones in {} to look for", region.name(size)),
+        ));
+    }
+
+    let library = PlaylistManager::new(Some(&args.library))?;
+    let search = PatternSearch::run(&pattern, &library);
+    for path in search.unreadable() {
+        eprintln!("Skipped unreadable game: {}", path.display());
+    }
+    eprintln!(
+        "{} with {} stone{}: found {} time{} in {} of {} games",
+        region.name(size),
+        pattern.stones(),
+        if pattern.stones() == 1 { "" } else { "s" },
+        search.hits().len(),
+        if search.hits().len() == 1 { "" } else { "s" },
+        search.matching_games(),
+        search.games()
+    );
+
+    let describe = |continuation: &Option<_>| match continuation {
+        Some(crate::pattern::Continuation { position, color }) => {
+            format!("{} {}", color_name(color), point_name(*position, size))
+        }
+        None => "none".to_string(),
+    };
+    let mut out = String::from("Next move\tTimes\n");
+    for (continuation, count) in search.continuations() {
+        out.push_str(&format!("{}\t{}\n", describe(&continuation), count));
+    }
+    out.push('\n');
+    for hit in search.hits() {
+        out.push_str(&format!(
+            "{}\tmove {}\t{}\n",
+            hit.path.display(),
+            hit.move_number,
+            describe(&hit.continuation)
+        ));
+    }
+    io::stdout().write_all(out.as_bytes())
+}
+
+fn color_name(color: &crate::parser::Color) -> &'static str {
+    match color {
+        crate::parser::Color::Black => "Black",
+        crate::parser::Color::White => "White",
+    }
+}
+
+// Write to the given file, or stdout when no file was given
+fn write_output(output: Option<&str>, content: &[u8]) -> Result<(), io::Error> {
+    match output {
+        Some(path) => fs::write(path, content),
+        None => io::stdout().write_all(content),
+    }
+}
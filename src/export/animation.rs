@@ -0,0 +1,139 @@
+use super::png::encode_png;
+use super::raster::{render_image, Image};
+use super::DiagramOptions;
+use crate::game::GameState;
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn from_name(name: &str) -> Option<AnimationFormat> {
+        match name.to_ascii_lowercase().as_str() {
+            "gif" => Some(AnimationFormat::Gif),
+            "apng" | "png" => Some(AnimationFormat::Apng),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationOptions {
+    pub diagram: DiagramOptions,
+    pub pixel_size: u32,
+    pub frame_delay_ms: u32,
+    pub first_move: usize,        // Position shown in the first frame
+    pub last_move: Option<usize>, // Position shown in the last frame (end of game when None)
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            diagram: DiagramOptions::default(),
+            pixel_size: 480,
+            frame_delay_ms: 500,
+            first_move: 0,
+            last_move: None,
+        }
+    }
+}
+
+// Replay `game` over the requested move range, one image per position
+pub fn render_frames(game: &mut GameState, options: &AnimationOptions) -> Vec<Image> {
+    let last = options
+        .last_move
+        .unwrap_or(game.moves.len())
+        .min(game.moves.len());
+    let first = options.first_move.min(last);
+
+    game.jump_to_move(first);
+    let mut frames = vec![render_image(game, &options.diagram, options.pixel_size)];
+    while game.current_move < last {
+        game.next();
+        frames.push(render_image(game, &options.diagram, options.pixel_size));
+    }
+    frames
+}
+
+pub fn render_animation(
+    game: &mut GameState,
+    options: &AnimationOptions,
+    format: AnimationFormat,
+) -> Result<Vec<u8>, io::Error> {
+    let frames = render_frames(game, options);
+    match format {
+        AnimationFormat::Gif => encode_gif(&frames, options.frame_delay_ms),
+        AnimationFormat::Apng => encode_apng(&frames, options.frame_delay_ms),
+    }
+}
+
+// Looping GIF; each frame gets its own quantized palette
+pub fn encode_gif(frames: &[Image], frame_delay_ms: u32) -> Result<Vec<u8>, io::Error> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "No frames to encode",
+        ));
+    };
+    let (width, height) = gif_dimensions(first)?;
+    let to_io = |e: gif::EncodingError| io::Error::other(e);
+
+    let mut data = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut data, width, height, &[]).map_err(to_io)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(to_io)?;
+
+        // GIF delays are in hundredths of a second
+        let delay = (frame_delay_ms / 10).clamp(1, u16::MAX as u32) as u16;
+        for image in frames {
+            let mut frame = gif::Frame::from_rgb_speed(width, height, &image.pixels, 30);
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(to_io)?;
+        }
+    }
+
+    Ok(data)
+}
+
+fn gif_dimensions(image: &Image) -> Result<(u16, u16), io::Error> {
+    match (u16::try_from(image.width), u16::try_from(image.height)) {
+        (Ok(width), Ok(height)) => Ok((width, height)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "GIF frames must be at most 65535 pixels wide",
+        )),
+    }
+}
+
+// Looping animated PNG; lossless, so frames keep their anti-aliasing exactly
+pub fn encode_apng(frames: &[Image], frame_delay_ms: u32) -> Result<Vec<u8>, io::Error> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "No frames to encode",
+        ));
+    };
+
+    // A single frame is just a still PNG
+    if frames.len() == 1 {
+        return encode_png(first);
+    }
+
+    let mut data = Vec::new();
+    let mut encoder = ::png::Encoder::new(&mut data, first.width, first.height);
+    encoder.set_color(::png::ColorType::Rgb);
+    encoder.set_depth(::png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    encoder.set_frame_delay(frame_delay_ms.min(u16::MAX as u32) as u16, 1000)?;
+
+    let mut writer = encoder.write_header()?;
+    for image in frames {
+        writer.write_image_data(&image.pixels)?;
+    }
+    writer.finish()?;
+
+    Ok(data)
+}
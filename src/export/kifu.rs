@@ -0,0 +1,188 @@
+use super::svg::render_board_svg;
+use super::DiagramOptions;
+use crate::game::GameState;
+use crate::parser::Color;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[derive(Debug, Clone)]
+pub struct KifuOptions {
+    pub diagram: DiagramOptions,
+    pub moves_per_diagram: usize,
+}
+
+impl Default for KifuOptions {
+    fn default() -> Self {
+        KifuOptions {
+            diagram: DiagramOptions::default(),
+            moves_per_diagram: 50,
+        }
+    }
+}
+
+// One printed figure: the moves it numbers and the notes printed under it
+pub struct KifuDiagram {
+    pub first_move: usize, // 1-based number of the first move shown
+    pub last_move: usize,  // 1-based number of the last move shown
+    pub svg: String,
+    pub notes: Vec<String>, // "52 at 40" for stones played on a numbered point, passes
+    pub comments: Vec<(usize, String)>,
+}
+
+const STYLE: &str = "\
+body { font-family: serif; max-width: 48em; margin: 2em auto; color: #000; }
+h1 { text-align: center; font-size: 1.6em; }
+table.info { border-collapse: collapse; margin: 0 auto 2em; }
+table.info th, table.info td { border: 1px solid #999; padding: 0.2em 0.8em; text-align: left; }
+section.diagram { break-inside: avoid; page-break-inside: avoid; margin-bottom: 2em; }
+section.diagram h2 { font-size: 1.1em; text-align: center; }
+section.diagram svg { display: block; margin: 0 auto; width: 100%; max-width: 32em; height: auto; }
+p.notes { text-align: center; }
+p.comment { white-space: pre-wrap; }
+ul.comments { list-style: none; padding: 0; }
+ul.comments li { margin: 0.4em 0; white-space: pre-wrap; }
+@media print { body { margin: 0; max-width: none; } section.diagram { page-break-after: always; } }
+";
+
+// Split the game into diagrams of `moves_per_diagram` moves each. Every
+// diagram shows the stones already on the board unnumbered and the new moves
+// numbered; a move on a point that already carries a number in the same
+// diagram is listed as a note instead, as in printed game records
+pub fn kifu_diagrams(game: &mut GameState, options: &KifuOptions) -> Vec<KifuDiagram> {
+    let per_diagram = options.moves_per_diagram.max(1);
+    let total = game.moves.len();
+    let mut diagrams = Vec::new();
+
+    let mut start = 0;
+    while start < total {
+        let end = (start + per_diagram).min(total);
+        game.jump_to_move(start);
+        let mut board = game.board.clone();
+        let mut numbered: HashMap<(u8, u8), usize> = HashMap::new();
+        let mut notes = Vec::new();
+        let mut comments = Vec::new();
+
+        for (index, mv) in game.moves[start..end].iter().enumerate() {
+            let number = start + index + 1;
+            match mv.position {
+                Some(pos) => match numbered.get(&pos) {
+                    Some(&earlier) => notes.push(format!("{} at {}", number, earlier)),
+                    None => {
+                        numbered.insert(pos, number);
+                        board.set(pos.0, pos.1, mv.color.clone());
+                    }
+                },
+                None => notes.push(format!("{}: {} passes", number, color_name(&mv.color))),
+            }
+            if let Some(comment) = mv.comment.as_deref().map(str::trim) {
+                if !comment.is_empty() {
+                    comments.push((number, comment.to_string()));
+                }
+            }
+        }
+
+        let svg = render_board_svg(
+            &board,
+            &options.diagram,
+            |row, col| numbered.get(&(row, col)).copied(),
+            None,
+        );
+        diagrams.push(KifuDiagram {
+            first_move: start + 1,
+            last_move: end,
+            svg,
+            notes,
+            comments,
+        });
+        start = end;
+    }
+
+    game.jump_to_end();
+    diagrams
+}
+
+// Render the whole game as a self-contained printable HTML page
+pub fn render_kifu_html(game: &mut GameState, options: &KifuOptions) -> String {
+    let black = game.get_property("PB").unwrap_or("Black").to_string();
+    let white = game.get_property("PW").unwrap_or("White").to_string();
+    let title = match game.get_property("GN") {
+        Some(name) => name.to_string(),
+        None => format!("{} vs {}", black, white),
+    };
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html>\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{}</title>", escape_html(&title));
+    let _ = writeln!(html, "<style>\n{}</style>\n</head>\n<body>", STYLE);
+    let _ = writeln!(html, "<h1>{}</h1>", escape_html(&title));
+
+    let properties = game.known_properties();
+    if !properties.is_empty() {
+        let _ = writeln!(html, "<table class=\"info\">");
+        for (name, value) in &properties {
+            let _ = writeln!(
+                html,
+                "<tr><th>{}</th><td>{}</td></tr>",
+                escape_html(name),
+                escape_html(value)
+            );
+        }
+        let _ = writeln!(html, "</table>");
+    }
+    // The game comment, which belongs to no move
+    if let Some(comment) = game.get_property("C").map(str::trim) {
+        if !comment.is_empty() {
+            let _ = writeln!(html, "<p class=\"comment\">{}</p>", escape_html(comment));
+        }
+    }
+
+    for diagram in kifu_diagrams(game, options) {
+        let _ = writeln!(html, "<section class=\"diagram\">");
+        let _ = writeln!(
+            html,
+            "<h2>Moves {}–{}</h2>",
+            diagram.first_move, diagram.last_move
+        );
+        html.push_str(&diagram.svg);
+        if !diagram.notes.is_empty() {
+            let _ = writeln!(
+                html,
+                "<p class=\"notes\">{}</p>",
+                escape_html(&diagram.notes.join(", "))
+            );
+        }
+        if !diagram.comments.is_empty() {
+            let _ = writeln!(html, "<ul class=\"comments\">");
+            for (number, comment) in &diagram.comments {
+                let _ = writeln!(html, "<li><b>{}</b> {}</li>", number, escape_html(comment));
+            }
+            let _ = writeln!(html, "</ul>");
+        }
+        let _ = writeln!(html, "</section>");
+    }
+
+    let _ = writeln!(html, "</body>\n</html>");
+    html
+}
+
+fn color_name(color: &Color) -> &'static str {
+    match color {
+        Color::Black => "Black",
+        Color::White => "White",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
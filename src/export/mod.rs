@@ -0,0 +1,138 @@
+pub mod animation;
+pub mod kifu;
+pub mod png;
+pub mod raster;
+pub mod svg;
+pub mod text;
+
+use crate::game::{load_game_from_path, GameState};
+use std::io;
+use std::path::Path;
+
+pub type Rgb = (u8, u8, u8);
+
+// Color schemes for exported diagrams; Wood matches the TUI board
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Theme {
+    Wood,
+    Light,
+    Dark,
+}
+
+pub struct Palette {
+    pub board: Rgb,
+    pub line: Rgb, // Grid, star points and stone outlines
+    pub black_stone: Rgb,
+    pub white_stone: Rgb,
+    pub label: Rgb,
+    pub highlight: Rgb, // Last move number
+}
+
+impl Theme {
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name.to_ascii_lowercase().as_str() {
+            "wood" => Some(Theme::Wood),
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Wood => "wood",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self {
+            Theme::Wood => Palette {
+                board: (210, 180, 140),
+                line: (0, 0, 0),
+                black_stone: (0, 0, 0),
+                white_stone: (255, 255, 255),
+                label: (0, 0, 0),
+                highlight: (221, 0, 0),
+            },
+            Theme::Light => Palette {
+                board: (245, 245, 240),
+                line: (85, 85, 85),
+                black_stone: (17, 17, 17),
+                white_stone: (255, 255, 255),
+                label: (85, 85, 85),
+                highlight: (221, 0, 0),
+            },
+            Theme::Dark => Palette {
+                board: (43, 43, 43),
+                line: (136, 136, 136),
+                black_stone: (0, 0, 0),
+                white_stone: (238, 238, 238),
+                label: (187, 187, 187),
+                highlight: (255, 85, 85),
+            },
+        }
+    }
+}
+
+// Options shared by the diagram exporters
+#[derive(Debug, Clone)]
+pub struct DiagramOptions {
+    pub coordinates: bool,  // Column letters and row numbers around the board
+    pub move_numbers: bool, // Number each stone with the move that placed it
+    pub rotation: u8,       // Same encoding as GameState: 0=0°, 1=90°, 2=180°, 3=270°
+    pub theme: Theme,
+}
+
+impl Default for DiagramOptions {
+    fn default() -> Self {
+        DiagramOptions {
+            coordinates: true,
+            move_numbers: false,
+            rotation: 0,
+            theme: Theme::Wood,
+        }
+    }
+}
+
+// Margin around the grid, in multiples of the line spacing
+pub(crate) fn margin_cells(options: &DiagramOptions) -> f32 {
+    if options.coordinates {
+        1.5
+    } else {
+        0.75
+    }
+}
+
+// Load a game and position it after `move_number` (the final position when None)
+pub fn load_position(path: &Path, move_number: Option<usize>) -> Result<GameState, io::Error> {
+    let mut game = load_game_from_path(path)?;
+    match move_number {
+        Some(n) => game.jump_to_move(n),
+        None => game.jump_to_end(),
+    }
+    Ok(game)
+}
+
+// Go diagrams traditionally skip 'I' in column letters
+pub fn column_label(col: u8) -> char {
+    let letter = b'A' + col;
+    if letter >= b'I' {
+        (letter + 1) as char
+    } else {
+        letter as char
+    }
+}
+
+// Rows are numbered from the bottom edge
+pub fn row_label(row: u8, size: u8) -> String {
+    (size - row).to_string()
+}
+
+// Board position of the most recent move, if it wasn't a pass
+pub(crate) fn last_move_position(game: &GameState) -> Option<(u8, u8)> {
+    game.current_move
+        .checked_sub(1)
+        .and_then(|idx| game.moves[idx].position)
+}
@@ -0,0 +1,26 @@
+use super::raster::{render_image, Image};
+use super::DiagramOptions;
+use crate::game::GameState;
+use std::io;
+
+pub fn encode_png(image: &Image) -> Result<Vec<u8>, io::Error> {
+    let mut data = Vec::new();
+    let mut encoder = ::png::Encoder::new(&mut data, image.width, image.height);
+    encoder.set_color(::png::ColorType::Rgb);
+    encoder.set_depth(::png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.pixels)?;
+    writer.finish()?;
+
+    Ok(data)
+}
+
+// Render the current position of `game` as PNG bytes, `pixel_size` pixels square
+pub fn render_png(
+    game: &GameState,
+    options: &DiagramOptions,
+    pixel_size: u32,
+) -> Result<Vec<u8>, io::Error> {
+    encode_png(&render_image(game, options, pixel_size))
+}
@@ -0,0 +1,232 @@
+use super::{column_label, last_move_position, margin_cells, row_label, DiagramOptions, Rgb};
+use crate::board_view::BoardView;
+use crate::game::GameState;
+use crate::parser::Color;
+
+// 5x7 bitmap glyphs for coordinates and move numbers; each row uses the low 5 bits
+const GLYPHS: &[(char, [u8; 7])] = &[
+    ('0', [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E]),
+    ('1', [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E]),
+    ('2', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F]),
+    ('3', [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E]),
+    ('4', [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02]),
+    ('5', [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E]),
+    ('6', [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E]),
+    ('7', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+    ('8', [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E]),
+    ('9', [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C]),
+    ('A', [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11]),
+    ('B', [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E]),
+    ('C', [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E]),
+    ('D', [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C]),
+    ('E', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F]),
+    ('F', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10]),
+    ('G', [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F]),
+    ('H', [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11]),
+    ('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C]),
+    ('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11]),
+    ('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F]),
+    ('M', [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11]),
+    ('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11]),
+    ('O', [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E]),
+    ('P', [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10]),
+    ('Q', [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D]),
+    ('R', [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11]),
+    ('S', [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E]),
+    ('T', [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+];
+
+// An RGB pixel buffer, row-major with 3 bytes per pixel
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn new(width: u32, height: u32, background: Rgb) -> Self {
+        let (r, g, b) = background;
+        Image {
+            width,
+            height,
+            pixels: [r, g, b].repeat(width as usize * height as usize),
+        }
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Rgb {
+        let idx = (y as usize * self.width as usize + x as usize) * 3;
+        (self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2])
+    }
+
+    // Mix `color` into the pixel with the given coverage; out-of-bounds is ignored
+    fn blend(&mut self, x: i64, y: i64, color: Rgb, alpha: f32) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 || alpha <= 0.0 {
+            return;
+        }
+        let alpha = alpha.min(1.0);
+        let idx = (y as usize * self.width as usize + x as usize) * 3;
+        for (channel, value) in [color.0, color.1, color.2].into_iter().enumerate() {
+            let old = self.pixels[idx + channel] as f32;
+            self.pixels[idx + channel] = (old + (value as f32 - old) * alpha).round() as u8;
+        }
+    }
+
+    fn fill_rect(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, color: Rgb) {
+        for y in y0.round() as i64..y1.round() as i64 {
+            for x in x0.round() as i64..x1.round() as i64 {
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    // Anti-aliased disc: coverage falls off over one pixel at the edge
+    fn fill_circle(&mut self, cx: f32, cy: f32, radius: f32, color: Rgb) {
+        self.for_each_near(cx, cy, radius + 1.0, |image, x, y, dist| {
+            image.blend(x, y, color, radius + 0.5 - dist);
+        });
+    }
+
+    fn stroke_circle(&mut self, cx: f32, cy: f32, radius: f32, width: f32, color: Rgb) {
+        self.for_each_near(cx, cy, radius + width, |image, x, y, dist| {
+            image.blend(x, y, color, width / 2.0 + 0.5 - (dist - radius).abs());
+        });
+    }
+
+    // Visit pixels within `reach` of (cx, cy) with the distance to their centers
+    fn for_each_near(
+        &mut self,
+        cx: f32,
+        cy: f32,
+        reach: f32,
+        mut visit: impl FnMut(&mut Self, i64, i64, f32),
+    ) {
+        for y in (cy - reach).floor() as i64..=(cy + reach).ceil() as i64 {
+            for x in (cx - reach).floor() as i64..=(cx + reach).ceil() as i64 {
+                let dist = ((x as f32 + 0.5 - cx).powi(2) + (y as f32 + 0.5 - cy).powi(2)).sqrt();
+                visit(self, x, y, dist);
+            }
+        }
+    }
+
+    // Draw text centered on (cx, cy); characters without a glyph are skipped
+    fn draw_text(&mut self, cx: f32, cy: f32, text: &str, scale: u32, color: Rgb) {
+        let glyphs: Vec<&[u8; 7]> = text
+            .chars()
+            .filter_map(|ch| GLYPHS.iter().find(|(c, _)| *c == ch).map(|(_, g)| g))
+            .collect();
+        if glyphs.is_empty() {
+            return;
+        }
+
+        let scale = scale as i64;
+        let width = glyphs.len() as i64 * 6 * scale - scale;
+        let left = (cx - width as f32 / 2.0).round() as i64;
+        let top = (cy - 7.0 * scale as f32 / 2.0).round() as i64;
+
+        for (i, glyph) in glyphs.iter().enumerate() {
+            let glyph_left = left + i as i64 * 6 * scale;
+            for (row, bits) in glyph.iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0x10 >> col) == 0 {
+                        continue;
+                    }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            let x = glyph_left + col * scale + dx;
+                            let y = top + row as i64 * scale + dy;
+                            self.blend(x, y, color, 1.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Rasterise the current position of `game` into a square image `pixel_size` wide
+pub fn render_image(game: &GameState, options: &DiagramOptions, pixel_size: u32) -> Image {
+    let palette = options.theme.palette();
+    let view = BoardView::new(&game.board, options.rotation);
+    let size = view.size();
+
+    let cell = pixel_size as f32 / ((size - 1) as f32 + 2.0 * margin_cells(options));
+    let margin = cell * margin_cells(options);
+    let extent = cell * (size - 1) as f32;
+    let at = |index: u8| margin + cell * index as f32;
+    let line_width = (cell / 24.0).round().max(1.0);
+    let stone_radius = cell * 0.47;
+
+    let mut image = Image::new(pixel_size, pixel_size, palette.board);
+
+    // Grid lines
+    let half = line_width / 2.0;
+    for i in 0..size {
+        let pos = at(i);
+        let (start, end) = (margin - half, margin + extent + half);
+        image.fill_rect(start, pos - half, end, pos + half, palette.line);
+        image.fill_rect(pos - half, start, pos + half, end, palette.line);
+    }
+
+    // Coordinates on all four sides
+    if options.coordinates {
+        let scale = (cell * 0.45 / 7.0).round().max(1.0) as u32;
+        let near = margin - cell * 0.9;
+        let far = margin + extent + cell * 0.9;
+        for i in 0..size {
+            let pos = at(i);
+            let col = column_label(i).to_string();
+            let row = row_label(i, size);
+            image.draw_text(pos, near, &col, scale, palette.label);
+            image.draw_text(pos, far, &col, scale, palette.label);
+            image.draw_text(near, pos, &row, scale, palette.label);
+            image.draw_text(far, pos, &row, scale, palette.label);
+        }
+    }
+
+    let star_points = game.board.star_points();
+    let last_move = last_move_position(game);
+    // Three digits must fit inside a stone
+    let number_scale = (stone_radius * 1.6 / 17.0)
+        .min(cell * 0.4 / 7.0)
+        .floor()
+        .max(1.0) as u32;
+
+    for view_row in 0..size {
+        for view_col in 0..size {
+            let (x, y) = (at(view_col), at(view_row));
+            let board_pos = view.to_board(view_row, view_col);
+
+            let Some(color) = view.get(view_row, view_col) else {
+                if star_points.contains(&board_pos) {
+                    image.fill_circle(x, y, (cell * 0.1).max(1.5), palette.line);
+                }
+                continue;
+            };
+
+            let (fill, contrast) = match color {
+                Color::Black => (palette.black_stone, palette.white_stone),
+                Color::White => (palette.white_stone, palette.black_stone),
+            };
+            image.fill_circle(x, y, stone_radius, palette.line);
+            image.fill_circle(x, y, stone_radius - line_width, fill);
+
+            let is_last = last_move == Some(board_pos);
+            let number = game.move_number_at(board_pos.0, board_pos.1);
+
+            match number.filter(|_| options.move_numbers) {
+                Some(number) => {
+                    // The last move's number is highlighted instead of drawing a marker
+                    let text_color = if is_last { palette.highlight } else { contrast };
+                    image.draw_text(x, y, &number.to_string(), number_scale, text_color);
+                }
+                None if is_last => {
+                    image.stroke_circle(x, y, stone_radius * 0.5, line_width * 2.0, contrast);
+                }
+                None => {}
+            }
+        }
+    }
+
+    image
+}
@@ -0,0 +1,144 @@
+use super::{column_label, last_move_position, margin_cells, row_label, DiagramOptions, Rgb};
+use crate::board_view::BoardView;
+use crate::game::{Board, GameState};
+use crate::parser::Color;
+use std::fmt::Write;
+
+const CELL: f32 = 24.0; // Distance between lines in SVG user units
+const STONE_RADIUS: f32 = CELL * 0.47;
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// Render the current position of `game` as a standalone SVG document
+pub fn render_svg(game: &GameState, options: &DiagramOptions) -> String {
+    let mut svg = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    svg.push_str(&render_board_svg(
+        &game.board,
+        options,
+        |row, col| {
+            game.move_number_at(row, col)
+                .filter(|_| options.move_numbers)
+        },
+        last_move_position(game),
+    ));
+    svg
+}
+
+// Render `board` as an <svg> element, numbering stones with `number_at` and
+// marking `last_move`; also used for the diagrams embedded in kifu pages
+pub(crate) fn render_board_svg(
+    board: &Board,
+    options: &DiagramOptions,
+    number_at: impl Fn(u8, u8) -> Option<usize>,
+    last_move: Option<(u8, u8)>,
+) -> String {
+    let view = BoardView::new(board, options.rotation);
+    let size = view.size();
+    let palette = options.theme.palette();
+    let (background, line, label) = (hex(palette.board), hex(palette.line), hex(palette.label));
+    let margin = CELL * margin_cells(options);
+    let extent = CELL * (size - 1) as f32;
+    let total = extent + margin * 2.0;
+    let at = |index: u8| margin + CELL * index as f32;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="{total}" viewBox="0 0 {total} {total}">"##
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="{total}" height="{total}" fill="{background}"/>"##
+    );
+
+    // Grid lines
+    let _ = writeln!(svg, r##"<g stroke="{line}" stroke-width="1">"##);
+    for i in 0..size {
+        let pos = at(i);
+        let (start, end) = (margin, margin + extent);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{start}" y1="{pos}" x2="{end}" y2="{pos}"/>"##
+        );
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{pos}" y1="{start}" x2="{pos}" y2="{end}"/>"##
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+
+    // Coordinates on all four sides
+    if options.coordinates {
+        let near = margin - CELL * 0.9;
+        let far = margin + extent + CELL * 0.9;
+        let _ = writeln!(
+            svg,
+            r##"<g font-family="sans-serif" font-size="11" fill="{label}" text-anchor="middle" dominant-baseline="central">"##
+        );
+        for i in 0..size {
+            let pos = at(i);
+            let col = column_label(i);
+            let row = row_label(i, size);
+            let _ = writeln!(svg, r##"<text x="{pos}" y="{near}">{col}</text>"##);
+            let _ = writeln!(svg, r##"<text x="{pos}" y="{far}">{col}</text>"##);
+            let _ = writeln!(svg, r##"<text x="{near}" y="{pos}">{row}</text>"##);
+            let _ = writeln!(svg, r##"<text x="{far}" y="{pos}">{row}</text>"##);
+        }
+        let _ = writeln!(svg, "</g>");
+    }
+
+    let star_points = board.star_points();
+
+    for view_row in 0..size {
+        for view_col in 0..size {
+            let (x, y) = (at(view_col), at(view_row));
+            let board_pos = view.to_board(view_row, view_col);
+
+            let Some(color) = view.get(view_row, view_col) else {
+                if star_points.contains(&board_pos) {
+                    let _ = writeln!(svg, r##"<circle cx="{x}" cy="{y}" r="3" fill="{line}"/>"##);
+                }
+                continue;
+            };
+
+            let (fill, contrast) = match color {
+                Color::Black => (hex(palette.black_stone), hex(palette.white_stone)),
+                Color::White => (hex(palette.white_stone), hex(palette.black_stone)),
+            };
+            let _ = writeln!(
+                svg,
+                r##"<circle cx="{x}" cy="{y}" r="{STONE_RADIUS}" fill="{fill}" stroke="{line}" stroke-width="1"/>"##
+            );
+
+            let is_last = last_move == Some(board_pos);
+            match number_at(board_pos.0, board_pos.1) {
+                Some(number) => {
+                    // The last move's number is highlighted instead of drawing a marker
+                    let text_color = if is_last {
+                        hex(palette.highlight)
+                    } else {
+                        contrast
+                    };
+                    let font_size = if number >= 100 { 9 } else { 11 };
+                    let _ = writeln!(
+                        svg,
+                        r##"<text x="{x}" y="{y}" font-family="sans-serif" font-size="{font_size}" fill="{text_color}" text-anchor="middle" dominant-baseline="central">{number}</text>"##
+                    );
+                }
+                None if is_last => {
+                    let marker_radius = STONE_RADIUS * 0.5;
+                    let _ = writeln!(
+                        svg,
+                        r##"<circle cx="{x}" cy="{y}" r="{marker_radius}" fill="none" stroke="{contrast}" stroke-width="2"/>"##
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
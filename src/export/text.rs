@@ -0,0 +1,121 @@
+use super::{column_label, last_move_position, row_label, DiagramOptions};
+use crate::board_view::{BoardView, Intersection, BLACK_STONE, WHITE_STONE};
+use crate::game::GameState;
+use crate::parser::Color;
+use std::fmt::Write;
+
+const ANSI_BOARD: &str = "\x1b[48;2;210;180;140m"; // Tan wood background, as in the TUI
+const ANSI_GRID: &str = "\x1b[90m"; // Dark gray lines
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextStyle {
+    // Plain ASCII: X/O stones on a +-+ grid, safe for logs and chat
+    Ascii,
+    // The TUI's box-drawing glyphs and stone emoji with ANSI colors
+    Ansi,
+}
+
+// Render the current position of `game` as text, with a title and move line
+pub fn render_text(game: &GameState, options: &DiagramOptions, style: TextStyle) -> String {
+    let view = BoardView::new(&game.board, options.rotation);
+    let size = view.size();
+    let separator = match style {
+        TextStyle::Ascii => " | ",
+        TextStyle::Ansi => " │ ",
+    };
+    let mut out = String::new();
+
+    let black = game.get_property("PB").unwrap_or("Black");
+    let white = game.get_property("PW").unwrap_or("White");
+    match game.get_property("GN") {
+        Some(name) => {
+            let _ = writeln!(out, "{}{}{} vs {}", name, separator, black, white);
+        }
+        None => {
+            let _ = writeln!(out, "{} vs {}", black, white);
+        }
+    }
+
+    // Each intersection is 1 column (ASCII) or 2 (emoji width), plus a connector
+    let cell_width = match style {
+        TextStyle::Ascii => 2,
+        TextStyle::Ansi => 3,
+    };
+
+    if options.coordinates {
+        let mut labels = String::from("   ");
+        for col in 0..size {
+            let _ = write!(labels, "{:<width$}", column_label(col), width = cell_width);
+        }
+        let _ = writeln!(out, "{}", labels.trim_end());
+    }
+
+    for row in 0..size {
+        if options.coordinates {
+            let _ = write!(out, "{:>2} ", row_label(row, size));
+        }
+        if style == TextStyle::Ansi {
+            out.push_str(ANSI_BOARD);
+        }
+
+        for col in 0..size {
+            let stone = view.get(row, col);
+            let intersection = Intersection::at(row, col, size);
+            let connector = col < size - 1;
+
+            match style {
+                TextStyle::Ascii => {
+                    out.push(match stone {
+                        Some(Color::Black) => 'X',
+                        Some(Color::White) => 'O',
+                        None => '+',
+                    });
+                    if connector {
+                        out.push('-');
+                    }
+                }
+                TextStyle::Ansi => {
+                    match stone {
+                        Some(Color::Black) => out.push_str(BLACK_STONE),
+                        Some(Color::White) => out.push_str(WHITE_STONE),
+                        None => {
+                            let _ = write!(out, "{}{}", ANSI_GRID, intersection.regular_glyph());
+                        }
+                    }
+                    if connector {
+                        let _ = write!(out, "{}─", ANSI_GRID);
+                    }
+                }
+            }
+        }
+
+        if style == TextStyle::Ansi {
+            out.push_str(ANSI_RESET);
+        }
+        if options.coordinates {
+            let _ = write!(out, " {}", row_label(row, size));
+        }
+        out.push('\n');
+    }
+
+    let _ = write!(out, "Move {}/{}", game.current_move, game.moves.len());
+    if let Some(mv) = game.current_move.checked_sub(1).map(|idx| &game.moves[idx]) {
+        let color = match mv.color {
+            Color::Black => "Black",
+            Color::White => "White",
+        };
+        // Coordinates match the labels printed around the (possibly rotated) board
+        let position = match last_move_position(game) {
+            Some((board_row, board_col)) => {
+                let (row, col) = view.to_view(board_row, board_col);
+                format!("{}{}", column_label(col), row_label(row, size))
+            }
+            None => "Pass".to_string(),
+        };
+        let _ = write!(out, "{}{} {}", separator, color, position);
+    }
+    out.push('\n');
+
+    out
+}
@@ -0,0 +1,61 @@
+// Live follow mode, like `tail -f`: a game file that is still being recorded
+// is re-read whenever its size or modification time changes.
+
+use crate::archive::ArchiveCache;
+use crate::import::{self, GameFormat};
+use crate::parser::GameTree;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub struct FileFollower {
+    path: PathBuf,
+    stamp: Option<(SystemTime, u64)>, // Modification time and size at the last successful read
+}
+
+impl FileFollower {
+    pub fn new(path: &Path) -> Self {
+        FileFollower {
+            path: path.to_path_buf(),
+            stamp: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // The game as it is now on the first call and whenever the file has changed
+    // since. A half-written record that doesn't parse yet is retried next time
+    pub fn poll(&mut self) -> Option<GameTree> {
+        let stamp = file_stamp(&self.path)?;
+        if self.stamp == Some(stamp) {
+            return None;
+        }
+        let tree = read_growing_game(&self.path).ok()?;
+        self.stamp = Some(stamp);
+        Some(tree)
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+// Recorders that append moves as they are played often leave the closing
+// parenthesis off until the game is over
+pub fn read_growing_game(path: &Path) -> Result<GameTree, io::Error> {
+    let content = import::read_game_text(path, &mut ArchiveCache::default())?;
+    match import::parse_game_text(path, &content) {
+        Err(e) if GameFormat::from_path(path) == GameFormat::Sgf => {
+            let trimmed = content.trim_end();
+            if trimmed.ends_with(')') {
+                return Err(e);
+            }
+            import::parse_game_text(path, &format!("{})", trimmed))
+        }
+        result => result,
+    }
+}
@@ -1,10 +1,15 @@
-use crate::parser::{Color, Move};
+use crate::import::read_game_tree;
+use crate::parser::{self, Color, GameTree, Move};
+use crate::position::PositionHash;
 use std::collections::HashMap;
+use std::io;
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub struct Board {
     pub size: u8,
     grid: Vec<Vec<Option<Color>>>,
+    hash: PositionHash, // Kept up to date as stones are placed and removed
 }
 
 impl Board {
@@ -12,6 +17,7 @@ impl Board {
         Board {
             size,
             grid: vec![vec![None; size as usize]; size as usize],
+            hash: PositionHash::empty(size),
         }
     }
 
@@ -20,18 +26,160 @@ impl Board {
     }
 
     pub fn set(&mut self, row: u8, col: u8, color: Color) {
+        self.clear(row, col);
+        self.hash.toggle(self.size, (row, col), &color);
         self.grid[row as usize][col as usize] = Some(color);
     }
 
     pub fn clear(&mut self, row: u8, col: u8) {
-        self.grid[row as usize][col as usize] = None;
+        if let Some(color) = self.grid[row as usize][col as usize].take() {
+            self.hash.toggle(self.size, (row, col), &color);
+        }
+    }
+
+    // Play a stone as a move: opposing groups left without liberties are
+    // captured, then the stone's own group if it has none (suicide). Returns
+    // the captured points
+    pub fn play(&mut self, row: u8, col: u8, color: Color) -> Vec<(u8, u8)> {
+        self.set(row, col, color.clone());
+        let mut captured = Vec::new();
+        for neighbour in self.neighbours((row, col)) {
+            if self
+                .get(neighbour.0, neighbour.1)
+                .is_some_and(|stone| stone != color)
+            {
+                captured.extend(self.capture_group(neighbour));
+            }
+        }
+        if captured.is_empty() {
+            captured.extend(self.capture_group((row, col)));
+        }
+        captured
+    }
+
+    // Remove the group at `point` if it has no liberties
+    fn capture_group(&mut self, point: (u8, u8)) -> Vec<(u8, u8)> {
+        let Some(color) = self.get(point.0, point.1) else {
+            return Vec::new();
+        };
+        let mut group = vec![point];
+        let mut index = 0;
+        while index < group.len() {
+            for neighbour in self.neighbours(group[index]) {
+                match self.get(neighbour.0, neighbour.1) {
+                    None => return Vec::new(), // A liberty
+                    Some(stone) if stone == color && !group.contains(&neighbour) => {
+                        group.push(neighbour)
+                    }
+                    _ => {}
+                }
+            }
+            index += 1;
+        }
+        for &(row, col) in &group {
+            self.clear(row, col);
+        }
+        group
+    }
+
+    fn neighbours(&self, (row, col): (u8, u8)) -> impl Iterator<Item = (u8, u8)> {
+        let size = self.size;
+        [
+            (row.checked_sub(1), Some(col)),
+            (Some(row + 1).filter(|&r| r < size), Some(col)),
+            (Some(row), col.checked_sub(1)),
+            (Some(row), Some(col + 1).filter(|&c| c < size)),
+        ]
+        .into_iter()
+        .filter_map(|(row, col)| Some((row?, col?)))
+    }
+
+    pub fn hash(&self) -> &PositionHash {
+        &self.hash
     }
+
+    // Traditional hoshi positions for the board size (none for unusual sizes)
+    pub fn star_points(&self) -> Vec<(u8, u8)> {
+        let size = self.size;
+        let edge = match size {
+            s if s >= 13 => 3,
+            s if s >= 7 => 2,
+            _ => return Vec::new(),
+        };
+        let far = size - 1 - edge;
+        let mut points = vec![(edge, edge), (edge, far), (far, edge), (far, far)];
+
+        // Odd boards get tengen; large odd boards also get the side star points
+        if size % 2 == 1 {
+            let mid = size / 2;
+            points.push((mid, mid));
+            if size >= 19 {
+                points.extend([(edge, mid), (mid, edge), (mid, far), (far, mid)]);
+            }
+        }
+
+        points
+    }
+}
+
+// Human-readable names for well-known root properties, in display order
+pub const KNOWN_PROPERTIES: &[(&str, &str)] = &[
+    ("GN", "Game name"),
+    ("PB", "Black"),
+    ("BR", "Black rank"),
+    ("BT", "Black team"),
+    ("PW", "White"),
+    ("WR", "White rank"),
+    ("WT", "White team"),
+    ("EV", "Event"),
+    ("RO", "Round"),
+    ("DT", "Date"),
+    ("PC", "Place"),
+    ("RU", "Rules"),
+    ("SZ", "Board size"),
+    ("HA", "Handicap"),
+    ("KM", "Komi"),
+    ("TM", "Time limit"),
+    ("OT", "Overtime"),
+    ("RE", "Result"),
+    ("GC", "Game comment"),
+    ("AN", "Annotator"),
+    ("SO", "Source"),
+    ("US", "Recorded by"),
+    ("CP", "Copyright"),
+];
+
+// TM is given in seconds; show it as hours/minutes when it parses
+fn format_time_limit(value: &str) -> String {
+    let Ok(seconds) = value.trim().parse::<f64>() else {
+        return value.to_string();
+    };
+
+    let seconds = seconds as u64;
+    let (hours, minutes, secs) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    let mut parts = Vec::new();
+    if hours > 0 {
+        parts.push(format!("{}h", hours));
+    }
+    if minutes > 0 {
+        parts.push(format!("{}m", minutes));
+    }
+    if secs > 0 || parts.is_empty() {
+        parts.push(format!("{}s", secs));
+    }
+    parts.join(" ")
+}
+
+// Board size comes from SZ, falling back to 19x19 when it is missing or invalid
+fn board_size(game_tree: &GameTree) -> u8 {
+    game_tree.board_size().unwrap_or(19)
 }
 
 pub struct GameState {
     pub board: Board,
     pub moves: Vec<Move>,
     pub current_move: usize, // 0 = empty board, 1 = after first move, etc.
+    start_move: usize,       // Where the game opens; looping and Home come back here
     pub properties: HashMap<String, Vec<String>>, // Game metadata
     looping_enabled: bool,   // Whether to loop back to start when reaching the end
     rotation: u8,            // Board rotation: 0=0°, 1=90°, 2=180°, 3=270°
@@ -43,6 +191,7 @@ impl GameState {
             board: Board::new(board_size),
             moves,
             current_move: 0,
+            start_move: 0,
             properties: HashMap::new(),
             looping_enabled: true, // Default to looping enabled
             rotation: 0,
@@ -54,13 +203,36 @@ impl GameState {
         moves: Vec<Move>,
         properties: HashMap<String, Vec<String>>,
     ) -> Self {
-        GameState {
+        let mut game = GameState {
             board: Board::new(board_size),
             moves,
             current_move: 0,
+            start_move: 0,
             properties,
             looping_enabled: true, // Default to looping enabled
             rotation: 0,
+        };
+        game.board = game.start_board();
+        game
+    }
+
+    pub fn from_tree(game_tree: GameTree) -> Self {
+        let board_size = board_size(&game_tree);
+        GameState::with_properties(board_size, game_tree.moves, game_tree.properties)
+    }
+
+    // Swap in a newer version of the same game, e.g. a live record that has
+    // grown. The view stays at the latest move if it was at the end, and at
+    // the same move otherwise
+    pub fn update_from_tree(&mut self, game_tree: GameTree) {
+        let at_end = self.current_move >= self.moves.len();
+        self.board = Board::new(board_size(&game_tree));
+        self.moves = game_tree.moves;
+        self.properties = game_tree.properties;
+        if at_end {
+            self.jump_to_end();
+        } else {
+            self.jump_to_move(self.current_move);
         }
     }
 
@@ -71,6 +243,34 @@ impl GameState {
             .map(|s| s.as_str())
     }
 
+    // Known root properties as (human name, value) pairs, in KNOWN_PROPERTIES order
+    pub fn known_properties(&self) -> Vec<(&'static str, String)> {
+        KNOWN_PROPERTIES
+            .iter()
+            .filter_map(|(key, name)| {
+                let values = self.properties.get(*key)?;
+                let value = if *key == "TM" {
+                    format_time_limit(&values.join(", "))
+                } else {
+                    values.join(", ")
+                };
+                Some((*name, value))
+            })
+            .collect()
+    }
+
+    // Root properties not in KNOWN_PROPERTIES as raw (key, value) pairs, sorted by key
+    pub fn unknown_properties(&self) -> Vec<(&str, String)> {
+        let mut raw: Vec<(&str, String)> = self
+            .properties
+            .iter()
+            .filter(|(key, _)| !KNOWN_PROPERTIES.iter().any(|(known, _)| known == key))
+            .map(|(key, values)| (key.as_str(), values.join(", ")))
+            .collect();
+        raw.sort();
+        raw
+    }
+
     pub fn is_looping_enabled(&self) -> bool {
         self.looping_enabled
     }
@@ -87,6 +287,7 @@ impl GameState {
         self.rotation
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> bool {
         if self.current_move >= self.moves.len() {
             // At the end of the game
@@ -104,7 +305,7 @@ impl GameState {
         // Apply the move at current_move index
         if let Some(pos) = self.moves[self.current_move].position {
             self.board
-                .set(pos.0, pos.1, self.moves[self.current_move].color.clone());
+                .play(pos.0, pos.1, self.moves[self.current_move].color.clone());
         }
 
         self.current_move += 1;
@@ -119,30 +320,83 @@ impl GameState {
         self.current_move -= 1;
 
         // Rebuild board from scratch up to current position
-        self.board = Board::new(self.board.size);
+        self.board = self.start_board();
         for i in 0..self.current_move {
             if let Some(pos) = self.moves[i].position {
-                self.board.set(pos.0, pos.1, self.moves[i].color.clone());
+                self.board.play(pos.0, pos.1, self.moves[i].color.clone());
             }
         }
 
         true
     }
 
+    // Open the game at `move_number` rather than the empty board, e.g. for a
+    // playlist entry with a start move
+    pub fn set_start_move(&mut self, move_number: usize) {
+        self.start_move = move_number.min(self.moves.len());
+        self.jump_to_move(self.start_move);
+    }
+
     pub fn jump_to_start(&mut self) {
-        self.current_move = 0;
-        self.board = Board::new(self.board.size);
+        self.jump_to_move(self.start_move);
     }
 
     pub fn jump_to_end(&mut self) {
         // Jump directly to the end without triggering looping behavior
         self.current_move = 0;
-        self.board = Board::new(self.board.size);
+        self.board = self.start_board();
         for i in 0..self.moves.len() {
             if let Some(pos) = self.moves[i].position {
-                self.board.set(pos.0, pos.1, self.moves[i].color.clone());
+                self.board.play(pos.0, pos.1, self.moves[i].color.clone());
             }
         }
         self.current_move = self.moves.len();
     }
+
+    pub fn jump_to_move(&mut self, move_number: usize) {
+        // Rebuild board from scratch up to the requested move (clamped to the game length)
+        let target = move_number.min(self.moves.len());
+        self.board = self.start_board();
+        for i in 0..target {
+            if let Some(pos) = self.moves[i].position {
+                self.board.play(pos.0, pos.1, self.moves[i].color.clone());
+            }
+        }
+        self.current_move = target;
+    }
+
+    // The board before the first move: empty apart from the setup stones
+    // (AB, AW) of the root node, such as handicap stones. Setup inside the
+    // game's later nodes isn't replayed
+    pub fn start_board(&self) -> Board {
+        let mut board = Board::new(self.board.size);
+        for (key, color) in [("AB", Color::Black), ("AW", Color::White)] {
+            let values = self.properties.get(key).into_iter().flatten();
+            for (row, col) in values.flat_map(|value| parser::parse_point_list(value)) {
+                if row < board.size && col < board.size {
+                    board.set(row, col, color.clone());
+                }
+            }
+        }
+        board
+    }
+
+    // Hash of the position on the board, for searching other games for it
+    pub fn position_hash(&self) -> &PositionHash {
+        self.board.hash()
+    }
+
+    // Move number (1-based) of the move that placed the stone at (row, col), if any
+    pub fn move_number_at(&self, row: u8, col: u8) -> Option<usize> {
+        self.board.get(row, col)?; // Captured stones have left the board
+        self.moves[..self.current_move]
+            .iter()
+            .rposition(|mv| mv.position == Some((row, col)))
+            .map(|idx| idx + 1)
+    }
+}
+
+pub fn load_game_from_path(path: &Path) -> Result<GameState, io::Error> {
+    let game_tree = read_game_tree(path)?;
+    Ok(GameState::from_tree(game_tree))
 }
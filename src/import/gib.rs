@@ -0,0 +1,152 @@
+// Tygem .gib records: a header of \[KEY=value\] lines between \HS and \HE,
+// then the moves between \GS and \GE:
+//
+//   INI 0 1 <handicap> &4        game start
+//   STO 0 <n> <color> <x> <y>    stone, color 1 = black, 2 = white, 0-based from top-left
+//   SKI 0 <n>                    pass
+
+use super::{add_handicap, set_property, split_rank};
+use crate::parser::{Color, GameTree, Move, ParseError};
+use std::collections::HashMap;
+
+const SIZE: u8 = 19; // Tygem only records 19x19 games
+
+pub fn parse_gib(input: &str) -> Result<GameTree, ParseError> {
+    if !input.contains("\\GS") {
+        return Err(ParseError::InvalidFormat(
+            "Missing \\GS game section".to_string(),
+        ));
+    }
+
+    let mut header = HashMap::new();
+    let mut moves = Vec::new();
+    let mut handicap = 0;
+
+    for line in input.lines().map(str::trim) {
+        if let Some(entry) = line
+            .strip_prefix("\\[")
+            .and_then(|rest| rest.strip_suffix("\\]"))
+        {
+            if let Some((key, value)) = entry.split_once('=') {
+                header.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["INI", _, _, count, ..] => handicap = count.parse().unwrap_or(0),
+            ["STO", _, _, color, x, y, ..] => {
+                let color = match *color {
+                    "1" => Color::Black,
+                    "2" => Color::White,
+                    _ => continue,
+                };
+                let position = match (x.parse::<u8>(), y.parse::<u8>()) {
+                    (Ok(col), Ok(row)) if col < SIZE && row < SIZE => Some((row, col)),
+                    _ => None,
+                };
+                moves.push(Move {
+                    color,
+                    position,
+                    ..Default::default()
+                });
+            }
+            ["SKI", ..] => {
+                // Passes don't record a color; they alternate with the previous move
+                let color = match moves.last().map(|mv: &Move| &mv.color) {
+                    Some(Color::Black) => Color::White,
+                    Some(Color::White) => Color::Black,
+                    None if handicap >= 2 => Color::White,
+                    None => Color::Black,
+                };
+                moves.push(Move {
+                    color,
+                    position: None,
+                    ..Default::default()
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut properties = HashMap::new();
+    properties.insert("SZ".to_string(), vec![SIZE.to_string()]);
+    for (key, color, rank_key) in [("GAMEBLACKNAME", "PB", "BR"), ("GAMEWHITENAME", "PW", "WR")] {
+        if let Some(player) = header.get(key) {
+            let (name, rank) = split_rank(player);
+            set_property(&mut properties, color, &name);
+            if let Some(rank) = rank {
+                set_property(&mut properties, rank_key, &rank);
+            }
+        }
+    }
+    for (key, property) in [("GAMENAME", "GN"), ("GAMEPLACE", "PC")] {
+        if let Some(value) = header.get(key) {
+            set_property(&mut properties, property, value);
+        }
+    }
+    if let Some(date) = header.get("GAMEDATE").and_then(|date| parse_date(date)) {
+        set_property(&mut properties, "DT", &date);
+    }
+
+    // Komi and the result are most reliable in the machine-readable GAMEINFOMAIN
+    let info: HashMap<&str, &str> = header
+        .get("GAMEINFOMAIN")
+        .map(|info| {
+            info.split(',')
+                .filter_map(|field| field.split_once(':'))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(komi) = info.get("DUM").and_then(|dum| dum.parse::<f32>().ok()) {
+        set_property(&mut properties, "KM", &(komi / 1000.0).to_string());
+    }
+    let result = info
+        .get("GRLT")
+        .and_then(|grlt| result_from_code(grlt, info.get("ZIPSU").copied()));
+    match result {
+        Some(result) => set_property(&mut properties, "RE", &result),
+        None => {
+            if let Some(result) = header.get("GAMERESULT") {
+                set_property(&mut properties, "RE", result);
+            }
+        }
+    }
+
+    add_handicap(&mut properties, SIZE, handicap);
+
+    Ok(GameTree { properties, moves })
+}
+
+// GRLT codes: 0/1 black/white by points (ZIPSU is the margin in tenths),
+// 3/4 by resignation, 7/8 on time
+fn result_from_code(code: &str, margin: Option<&str>) -> Option<String> {
+    let points = || {
+        margin
+            .and_then(|zipsu| zipsu.parse::<f32>().ok())
+            .map(|tenths| (tenths / 10.0).to_string())
+    };
+    match code {
+        "0" => Some(format!("B+{}", points()?)),
+        "1" => Some(format!("W+{}", points()?)),
+        "3" => Some("B+R".to_string()),
+        "4" => Some("W+R".to_string()),
+        "7" => Some("B+T".to_string()),
+        "8" => Some("W+T".to_string()),
+        _ => None,
+    }
+}
+
+// "2016- 3-13-19-04-04" -> "2016-03-13"
+fn parse_date(date: &str) -> Option<String> {
+    let parts: Vec<u32> = date
+        .split('-')
+        .take(3)
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [year, month, day] => Some(format!("{:04}-{:02}-{:02}", year, month, day)),
+        _ => None,
+    }
+}
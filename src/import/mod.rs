@@ -0,0 +1,209 @@
+pub mod gib;
+pub mod ngf;
+pub mod ugf;
+
+use crate::archive::{self, ArchiveCache};
+use crate::parser::{coords_to_sgf, parse_sgf, GameTree, ParseError};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Game record formats the viewer can open, recognised by file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameFormat {
+    Sgf,
+    Gib, // Tygem
+    Ngf, // WBaduk / Cyberoro
+    Ugf, // Pandanet
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+impl GameFormat {
+    // Anything without a recognised extension is treated as SGF
+    pub fn from_path(path: &Path) -> GameFormat {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "gib" => GameFormat::Gib,
+            "ngf" => GameFormat::Ngf,
+            "ugf" | "ugi" => GameFormat::Ugf,
+            #[cfg(feature = "serde")]
+            "json" => GameFormat::Json,
+            _ => GameFormat::Sgf,
+        }
+    }
+
+    // Parse a game, rejecting one with moves or setup stones off its board
+    pub fn parse(&self, content: &str) -> Result<GameTree, ParseError> {
+        let tree = match self {
+            GameFormat::Sgf => parse_sgf(content),
+            GameFormat::Gib => gib::parse_gib(content),
+            GameFormat::Ngf => ngf::parse_ngf(content),
+            GameFormat::Ugf => ugf::parse_ugf(content),
+            #[cfg(feature = "serde")]
+            GameFormat::Json => crate::json::from_json(content),
+        }?;
+        tree.check_board()?;
+        Ok(tree)
+    }
+
+    // Server formats are often saved in the server's local encoding rather than UTF-8
+    fn legacy_encoding(&self) -> Option<&'static encoding_rs::Encoding> {
+        match self {
+            GameFormat::Gib | GameFormat::Ngf => Some(encoding_rs::EUC_KR),
+            GameFormat::Ugf => Some(encoding_rs::SHIFT_JIS),
+            _ => None,
+        }
+    }
+}
+
+// Extensions picked up when scanning a folder for games
+// Extensions picked up when scanning a folder for games
+const GAME_EXTENSIONS: &[&str] = &[
+    "sgf",
+    "gib",
+    "ngf",
+    "ugf",
+    "ugi",
+    #[cfg(feature = "serde")]
+    "json",
+];
+
+pub fn is_game_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            GAME_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+        .unwrap_or(false)
+}
+
+// Read and parse a game file in any supported format, or an entry inside an archive
+pub fn read_game_tree(path: &Path) -> Result<GameTree, io::Error> {
+    read_game_tree_cached(path, &mut ArchiveCache::default())
+}
+
+// As read_game_tree, reading archive entries through `archives`
+pub fn read_game_tree_cached(
+    path: &Path,
+    archives: &mut ArchiveCache,
+) -> Result<GameTree, io::Error> {
+    let content = read_game_text(path, archives)?;
+    parse_game_text(path, &content)
+}
+
+// The text of a game file or archive entry, decoded from the format's legacy
+// encoding when it isn't UTF-8
+pub(crate) fn read_game_text(
+    path: &Path,
+    archives: &mut ArchiveCache,
+) -> Result<String, io::Error> {
+    let bytes = match archive::split_entry_path(path) {
+        Some((archive, entry)) => archives.read_entry(archive, &entry),
+        None => fs::read(path),
+    }
+    .map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read {}: {}", path.display(), e),
+        )
+    })?;
+
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(content),
+        Err(e) => match GameFormat::from_path(path).legacy_encoding() {
+            Some(encoding) => Ok(encoding.decode(e.as_bytes()).0.into_owned()),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to read {}: {}", path.display(), e),
+            )),
+        },
+    }
+}
+
+pub(crate) fn parse_game_text(path: &Path, content: &str) -> Result<GameTree, io::Error> {
+    GameFormat::from_path(path).parse(content).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {}: {:?}", path.display(), e),
+        )
+    })
+}
+
+// Traditional fixed handicap placement on the star points
+pub(crate) fn handicap_points(size: u8, count: usize) -> Vec<(u8, u8)> {
+    let edge = match size {
+        13.. => 3,
+        7..=12 => 2,
+        _ => return Vec::new(),
+    };
+    let (near, far, mid) = (edge, size - 1 - edge, size / 2);
+    let centre_allowed = size % 2 == 1;
+
+    let mut points = vec![(near, far), (far, near), (far, far), (near, near)];
+    if count >= 6 && centre_allowed {
+        points.extend([(mid, near), (mid, far)]);
+    }
+    if count >= 8 && centre_allowed {
+        points.extend([(near, mid), (far, mid)]);
+    }
+    if count % 2 == 1 && count >= 5 && centre_allowed {
+        points.push((mid, mid));
+    }
+    points.truncate(count);
+    points
+}
+
+// Record handicap stones the way an SGF file would (HA and AB)
+pub(crate) fn add_handicap(properties: &mut HashMap<String, Vec<String>>, size: u8, count: usize) {
+    if count < 2 {
+        return;
+    }
+    properties.insert("HA".to_string(), vec![count.to_string()]);
+    let stones: Vec<String> = handicap_points(size, count)
+        .into_iter()
+        .map(coords_to_sgf)
+        .collect();
+    if !stones.is_empty() {
+        properties.insert("AB".to_string(), stones);
+    }
+}
+
+// "Lee Sedol (9D)" or "Lee Sedol 9D*" -> ("Lee Sedol", Some("9D"))
+pub(crate) fn split_rank(player: &str) -> (String, Option<String>) {
+    let player = player.trim();
+    if let Some(open) = player.rfind('(') {
+        if player.ends_with(')') {
+            let rank = player[open + 1..player.len() - 1].trim();
+            return (player[..open].trim().to_string(), Some(rank.to_string()));
+        }
+    }
+
+    let mut words: Vec<&str> = player.split_whitespace().collect();
+    let looks_like_rank = |word: &str| {
+        let word = word.trim_end_matches('*');
+        word.len() >= 2
+            && word[..word.len() - 1].chars().all(|ch| ch.is_ascii_digit())
+            && word.ends_with(|ch: char| "kKdDpP".contains(ch))
+    };
+    if words.len() > 1 && words.last().is_some_and(|word| looks_like_rank(word)) {
+        let rank = words.pop().unwrap().trim_end_matches('*').to_string();
+        return (words.join(" "), Some(rank));
+    }
+    (player.to_string(), None)
+}
+
+// Set a single-valued property, skipping empty values
+pub(crate) fn set_property(properties: &mut HashMap<String, Vec<String>>, key: &str, value: &str) {
+    let value = value.trim();
+    if !value.is_empty() {
+        properties.insert(key.to_string(), vec![value.to_string()]);
+    }
+}
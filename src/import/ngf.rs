@@ -0,0 +1,120 @@
+// WBaduk / Cyberoro .ngf records: twelve fixed header lines followed by one
+// "PM" line per move:
+//
+//   0 title, 1 board size, 2 white player, 3 black player, 4 server,
+//   5 handicap, 6 (unused), 7 komi, 8 date "20160313 [13:00]",
+//   9 (unused), 10 result text, 11 move count
+//
+//   PM<nn><color><x><y><x><y>  coordinates are letters with 'B' = 0, from
+//                              the top-left; anything off the board is a pass
+
+use super::{add_handicap, set_property, split_rank};
+use crate::parser::{Color, GameTree, Move, ParseError};
+use std::collections::HashMap;
+
+const HEADER_LINES: usize = 12;
+
+pub fn parse_ngf(input: &str) -> Result<GameTree, ParseError> {
+    let lines: Vec<&str> = input.lines().map(str::trim).collect();
+    if lines.len() < HEADER_LINES {
+        return Err(ParseError::InvalidFormat(
+            "NGF header is incomplete".to_string(),
+        ));
+    }
+
+    let size: u8 = lines[1]
+        .parse()
+        .ok()
+        .filter(|&size| (1..=19).contains(&size))
+        .ok_or_else(|| ParseError::InvalidFormat(format!("Invalid board size: {}", lines[1])))?;
+
+    let mut properties = HashMap::new();
+    properties.insert("SZ".to_string(), vec![size.to_string()]);
+    set_property(&mut properties, "GN", lines[0]);
+    for (line, color, rank_key) in [(lines[2], "PW", "WR"), (lines[3], "PB", "BR")] {
+        let (name, rank) = split_rank(line);
+        set_property(&mut properties, color, &name);
+        if let Some(rank) = rank {
+            set_property(&mut properties, rank_key, &rank);
+        }
+    }
+    set_property(&mut properties, "PC", lines[4]);
+
+    // Komi is stored as a whole number with the half point implied
+    if let Ok(mut komi) = lines[7].parse::<f32>() {
+        if komi.fract() == 0.0 {
+            komi += 0.5;
+        }
+        set_property(&mut properties, "KM", &komi.to_string());
+    }
+    if let Some(date) = parse_date(lines[8]) {
+        set_property(&mut properties, "DT", &date);
+    }
+    if let Some(result) = parse_result(lines[10]) {
+        set_property(&mut properties, "RE", &result);
+    }
+    add_handicap(&mut properties, size, lines[5].parse().unwrap_or(0));
+
+    let mut moves = Vec::new();
+    for line in &lines[HEADER_LINES..] {
+        let bytes = line.as_bytes();
+        if !line.starts_with("PM") || bytes.len() < 7 {
+            continue;
+        }
+        let color = match bytes[4] {
+            b'B' => Color::Black,
+            b'W' => Color::White,
+            _ => continue,
+        };
+        let col = bytes[5].wrapping_sub(b'B');
+        let row = bytes[6].wrapping_sub(b'B');
+        let position = (col < size && row < size).then_some((row, col));
+        moves.push(Move {
+            color,
+            position,
+            ..Default::default()
+        });
+    }
+
+    Ok(GameTree { properties, moves })
+}
+
+// "20160313 [13:00]" -> "2016-03-13"
+fn parse_date(line: &str) -> Option<String> {
+    let digits = line.split_whitespace().next()?;
+    if digits.len() != 8 || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!(
+        "{}-{}-{}",
+        &digits[..4],
+        &digits[4..6],
+        &digits[6..]
+    ))
+}
+
+// "White wins by resignation!", "Black wins by 3.5!", "White win by time!"
+fn parse_result(line: &str) -> Option<String> {
+    let lower = line.to_lowercase();
+    let winner = if lower.contains("white win") {
+        'W'
+    } else if lower.contains("black win") {
+        'B'
+    } else {
+        return None;
+    };
+
+    if lower.contains("resign") {
+        return Some(format!("{}+R", winner));
+    }
+    if lower.contains("time") {
+        return Some(format!("{}+T", winner));
+    }
+    let margin = lower
+        .split(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .find_map(|word| word.trim_matches('.').parse::<f32>().ok());
+    Some(match margin {
+        Some(points) => format!("{}+{}", winner, points),
+        None => format!("{}+", winner),
+    })
+}
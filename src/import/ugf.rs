@@ -0,0 +1,134 @@
+// Pandanet .ugf/.ugi records: INI-style sections. [Header] holds KEY=value
+// lines; [Data] holds one move per line:
+//
+//   <col><row>,<color><n>,<n>,<seconds>   e.g. "QD,B1,1,0"
+//
+// Columns are letters from the left with 'A' = 0, rows are letters from the
+// bottom with 'A' = the first line; anything off the board is a pass
+
+use super::{add_handicap, set_property, split_rank};
+use crate::parser::{Color, GameTree, Move, ParseError};
+use std::collections::HashMap;
+
+pub fn parse_ugf(input: &str) -> Result<GameTree, ParseError> {
+    let mut section = "";
+    let mut header: HashMap<String, String> = HashMap::new();
+    let mut data = Vec::new();
+
+    for line in input.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            section = &line[1..line.len() - 1];
+            continue;
+        }
+        match section {
+            "Header" => {
+                if let Some((key, value)) = line.split_once('=') {
+                    header.insert(key.trim().to_string(), value.trim().to_string());
+                }
+            }
+            "Data" if !line.is_empty() => data.push(line),
+            _ => {}
+        }
+    }
+
+    if header.is_empty() {
+        return Err(ParseError::InvalidFormat(
+            "Missing [Header] section".to_string(),
+        ));
+    }
+
+    let field = |key: &str, index: usize| {
+        header
+            .get(key)
+            .and_then(|value| value.split(',').nth(index))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let size: u8 = match field("Size", 0) {
+        Some(value) => value
+            .parse()
+            .ok()
+            .filter(|&size| (1..=19).contains(&size))
+            .ok_or_else(|| ParseError::InvalidFormat(format!("Invalid board size: {}", value)))?,
+        None => 19,
+    };
+
+    let mut properties = HashMap::new();
+    properties.insert("SZ".to_string(), vec![size.to_string()]);
+    for (key, color, rank_key) in [("PlayerB", "PB", "BR"), ("PlayerW", "PW", "WR")] {
+        if let Some(name) = field(key, 0) {
+            let (name, embedded_rank) = split_rank(name);
+            set_property(&mut properties, color, &name);
+            if let Some(rank) = field(key, 1).map(str::to_string).or(embedded_rank) {
+                set_property(&mut properties, rank_key, &rank);
+            }
+        }
+    }
+    for (key, property) in [("Title", "GN"), ("Place", "PC")] {
+        if let Some(value) = field(key, 0) {
+            set_property(&mut properties, property, value);
+        }
+    }
+    if let Some(date) = field("Date", 0) {
+        set_property(&mut properties, "DT", &date.replace('/', "-"));
+    }
+    if let Some(komi) = field("Hdcp", 1) {
+        set_property(&mut properties, "KM", komi);
+    }
+    if let Some(result) = parse_winner(field("Winner", 0), field("Winner", 1)) {
+        set_property(&mut properties, "RE", &result);
+    }
+    add_handicap(
+        &mut properties,
+        size,
+        field("Hdcp", 0).and_then(|h| h.parse().ok()).unwrap_or(0),
+    );
+
+    let mut moves = Vec::new();
+    for line in data {
+        let mut parts = line.split(',');
+        let (Some(point), Some(player)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let color = match player.as_bytes().first() {
+            Some(b'B') => Color::Black,
+            Some(b'W') => Color::White,
+            _ => continue,
+        };
+        let point = point.as_bytes();
+        let position = match point {
+            [col, row] => {
+                let col = col.wrapping_sub(b'A');
+                let line_from_bottom = row.wrapping_sub(b'A');
+                (col < size && line_from_bottom < size).then(|| (size - 1 - line_from_bottom, col))
+            }
+            _ => None,
+        };
+        moves.push(Move {
+            color,
+            position,
+            ..Default::default()
+        });
+    }
+
+    Ok(GameTree { properties, moves })
+}
+
+// "Winner=B,3.5" is a win by points; any other margin is taken as resignation
+fn parse_winner(winner: Option<&str>, margin: Option<&str>) -> Option<String> {
+    let winner = match winner? {
+        "B" => 'B',
+        "W" => 'W',
+        _ => return None,
+    };
+    Some(
+        match margin
+            .and_then(|m| m.parse::<f32>().ok())
+            .filter(|&m| m > 0.0)
+        {
+            Some(points) => format!("{}+{}", winner, points),
+            None => format!("{}+R", winner),
+        },
+    )
+}
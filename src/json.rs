@@ -0,0 +1,104 @@
+// JSON form of a GameTree, for tools that don't speak SGF. The schema is
+// documented in docs/json-format.md:
+//
+// {
+//   "properties": { "PB": ["Lee Sedol"], "SZ": ["19"], ... },
+//   "setup": { "black": [[3, 3], [15, 15]] },
+//   "moves": [
+//     { "color": "black", "position": [3, 15] },
+//     { "color": "white", "position": null, "comment": "Pass",
+//       "variations": [[{ "color": "white", "position": [2, 2] }]] }
+//   ]
+// }
+
+use crate::parser::{self, coords_to_sgf, GameTree, Move, ParseError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// The document as written; root setup stones (AB, AW) are taken out of the
+// properties into `setup`
+#[derive(Serialize, Deserialize)]
+struct JsonGame {
+    properties: BTreeMap<String, Vec<String>>, // Sorted, so output is stable
+    #[serde(default, skip_serializing_if = "Setup::is_empty")]
+    setup: Setup,
+    moves: Vec<Move>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Setup {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    black: Vec<(u8, u8)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    white: Vec<(u8, u8)>,
+}
+
+impl Setup {
+    fn is_empty(&self) -> bool {
+        self.black.is_empty() && self.white.is_empty()
+    }
+}
+
+const SETUP_KEYS: [&str; 2] = ["AB", "AW"];
+
+// Pretty-printed JSON with properties in key order, so output is stable
+pub fn to_json(tree: &GameTree) -> String {
+    let mut properties: BTreeMap<String, Vec<String>> = tree
+        .properties
+        .iter()
+        .map(|(key, values)| (key.clone(), values.clone()))
+        .collect();
+    let mut points = |key: &str| -> Vec<(u8, u8)> {
+        let values = properties.remove(key).unwrap_or_default();
+        values
+            .iter()
+            .flat_map(|value| parser::parse_point_list(value))
+            .collect()
+    };
+    let setup = Setup {
+        black: points(SETUP_KEYS[0]),
+        white: points(SETUP_KEYS[1]),
+    };
+
+    let game = JsonGame {
+        properties,
+        setup,
+        moves: tree.moves.clone(),
+    };
+    serde_json::to_string_pretty(&game).expect("game trees always serialize to JSON")
+}
+
+// Parse a JSON game, rejecting moves and setup stones that fall outside the board
+pub fn from_json(input: &str) -> Result<GameTree, ParseError> {
+    let game: JsonGame = serde_json::from_str(input)
+        .map_err(|e| ParseError::InvalidFormat(format!("Invalid JSON: {}", e)))?;
+
+    let mut tree = GameTree {
+        properties: game.properties.into_iter().collect(),
+        moves: game.moves,
+    };
+    let size = tree.board_size()?;
+
+    // Setup stones are checked before they become SGF points, which only
+    // reach 19 lines
+    for (key, points) in SETUP_KEYS.iter().zip([game.setup.black, game.setup.white]) {
+        if let Some((row, col)) = points
+            .iter()
+            .find(|(row, col)| *row >= size || *col >= size)
+        {
+            return Err(ParseError::InvalidFormat(format!(
+                "Setup stone at ({}, {}) is off the {}x{} board",
+                row, col, size, size
+            )));
+        }
+        if !points.is_empty() {
+            tree.properties.insert(
+                key.to_string(),
+                points.into_iter().map(coords_to_sgf).collect(),
+            );
+        }
+    }
+
+    tree.check_board()?;
+    Ok(tree)
+}
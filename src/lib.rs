@@ -1,4 +1,16 @@
+pub mod archive;
 pub mod board_view;
+pub mod bookmarks;
+pub mod export;
+pub mod follow;
 pub mod game;
+pub mod import;
+#[cfg(feature = "serde")]
+pub mod json;
 pub mod parser;
+pub mod pattern;
 pub mod playlist;
+pub mod playlist_file;
+pub mod position;
+pub mod resume;
+pub mod search;
@@ -1,71 +1,169 @@
+pub mod archive;
 pub mod board_view;
+pub mod bookmarks;
+mod cli;
+pub mod export;
+pub mod follow;
 pub mod game;
+pub mod import;
+#[cfg(feature = "serde")]
+pub mod json;
 pub mod parser;
+pub mod pattern;
 pub mod playlist;
+pub mod playlist_file;
+pub mod position;
+pub mod resume;
+pub mod search;
 mod ui;
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::env;
-use std::fs;
-use std::io;
+use std::io::{self, IsTerminal};
 
 use std::time::Instant;
 
-use playlist::PlaylistManager;
+use bookmarks::{Bookmark, BookmarkStore};
+use follow::FileFollower;
+use pattern::PatternSearch;
+use playlist::{PlaylistManager, RepeatMode};
+use resume::ViewerState;
+use search::GameSearch;
+use std::path::{Path, PathBuf};
 
 enum AppState {
     Playing {
-        game: game::GameState,
+        game: Box<game::GameState>,
         auto_play: bool,
         playback_speed: u64,
         last_auto_advance: Instant,
+        overlay: Option<ui::Overlay>,
     },
     Transition {
-        from_title: String,
         to_title: String,
         start_time: Instant,
     },
 }
 
-fn load_game_from_path(path: &std::path::Path) -> Result<game::GameState, io::Error> {
-    let sgf_content = fs::read_to_string(path).map_err(|e| {
+// How often a watched folder is rescanned
+const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+// How long the resume offer waits for an answer before starting over
+const RESUME_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+// Where this session is remembered for the next launch
+struct Resume {
+    state: ViewerState,
+    state_path: PathBuf,
+    source: PathBuf, // The path the playlist was opened from
+}
+
+// How often a followed game file is checked for new moves
+const FOLLOW_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+// Load a playlist entry, looping at its end unless repeat is off
+fn load_game(playlist: &PlaylistManager, index: usize) -> io::Result<game::GameState> {
+    let mut game = playlist.load(index)?;
+    game.set_looping(playlist.repeat() != RepeatMode::Off);
+    Ok(game)
+}
+
+// Load a playlist entry and make it the current one
+fn switch_game(playlist: &mut PlaylistManager, index: usize) -> io::Result<game::GameState> {
+    let game = load_game(playlist, index)?;
+    playlist.jump_to(index);
+    Ok(game)
+}
+
+// The bookmark file is re-read before every change so bookmarks other tools
+// added while the viewer runs aren't lost
+fn update_bookmarks(change: impl FnOnce(&mut BookmarkStore)) -> io::Result<Vec<Bookmark>> {
+    let path = BookmarkStore::default_path().ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::NotFound,
-            format!("Failed to read {}: {}", path.display(), e),
+            "No data folder to keep bookmarks in",
         )
     })?;
+    let mut store = BookmarkStore::load(&path)?;
+    change(&mut store);
+    store.save(&path)?;
+    Ok(store.bookmarks().to_vec())
+}
 
-    let game_tree = parser::parse_sgf(&sgf_content).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse SGF: {:?}", e),
-        )
-    })?;
+fn load_bookmarks() -> io::Result<Vec<Bookmark>> {
+    match BookmarkStore::default_path() {
+        Some(path) => Ok(BookmarkStore::load(&path)?.bookmarks().to_vec()),
+        None => Ok(Vec::new()),
+    }
+}
 
-    let board_size = game_tree
-        .properties
-        .get("SZ")
-        .and_then(|v| v.first())
-        .and_then(|s| s.parse::<u8>().ok())
-        .unwrap_or(19);
-
-    Ok(game::GameState::with_properties(
-        board_size,
-        game_tree.moves,
-        game_tree.properties,
-    ))
+// Open a bookmarked position, adding its game to the playlist if needed
+fn open_bookmark(
+    playlist: &mut PlaylistManager,
+    bookmark: &Bookmark,
+) -> io::Result<game::GameState> {
+    // Make sure the game still loads before it joins the playlist
+    game::load_game_from_path(&bookmark.path)?;
+    let index = playlist.find_or_add(&bookmark.path);
+    let mut game = switch_game(playlist, index)?;
+    game.jump_to_move(bookmark.move_number);
+    Ok(game)
+}
+
+fn cycle_repeat(playlist: &mut PlaylistManager, game: &mut game::GameState) {
+    playlist.set_repeat(playlist.repeat().cycle());
+    game.set_looping(playlist.repeat() != RepeatMode::Off);
+}
+
+fn toggle_shuffle(playlist: &mut PlaylistManager) {
+    if playlist.shuffle_seed().is_some() {
+        playlist.unshuffle();
+    } else {
+        playlist.shuffle(playlist::random_seed());
+    }
+}
+
+// Cycle playback speed 1x -> 2x -> 3x -> 1x
+fn next_playback_speed(speed: u64) -> u64 {
+    if speed >= 3 {
+        1
+    } else {
+        speed + 1
+    }
 }
 
 fn main() -> Result<(), io::Error> {
-    let args: Vec<String> = env::args().collect();
-    let path_arg = args.get(1).map(|s| s.as_str());
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = cli::parse_args(&args).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        std::process::exit(2);
+    });
+
+    let view = match command {
+        cli::Command::Svg(export_args) => return cli::run_svg(&export_args),
+        cli::Command::Png(export_args) => return cli::run_png(&export_args),
+        cli::Command::Animate(export_args) => return cli::run_animate(&export_args),
+        cli::Command::Dump(export_args) => return cli::run_dump(&export_args),
+        cli::Command::Kifu(export_args) => return cli::run_kifu(&export_args),
+        cli::Command::Find(find_args) => return cli::run_find(&find_args),
+        cli::Command::Pattern(find_args) => return cli::run_pattern(&find_args),
+        cli::Command::View(view) => view,
+    };
+
+    // With no arguments, piped input is read like "-"
+    let path_arg = view
+        .path
+        .as_deref()
+        .or_else(|| (!io::stdin().is_terminal()).then_some(playlist::STDIN_ARG));
 
-    let playlist = PlaylistManager::new(path_arg).map_err(|e| {
+    let mut playlist = PlaylistManager::with_options(path_arg, &view.scan).map_err(|e| {
         if path_arg.is_none() {
             io::Error::new(
                 io::ErrorKind::NotFound,
@@ -76,7 +174,63 @@ fn main() -> Result<(), io::Error> {
         }
     })?;
 
-    let initial_game = load_game_from_path(playlist.current())?;
+    if !view.query.is_empty() && playlist.filter(&view.query) == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No games match the given filters",
+        ));
+    }
+    if let Some(key) = view.sort {
+        playlist.sort(key);
+    }
+    playlist.set_repeat(view.repeat);
+    if let Some(seed) = view.shuffle {
+        playlist.shuffle(seed);
+        playlist.jump_to(0);
+    }
+    if let Some(output) = &view.save_playlist {
+        playlist.save(Path::new(output))?;
+        eprintln!("Saved {} games to {}", playlist.files().len(), output);
+        return Ok(());
+    }
+
+    if view.watch && !playlist.is_watchable() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--watch needs a folder to watch",
+        ));
+    }
+
+    let mut initial_game = load_game(&playlist, playlist.current_index())?;
+    if view.follow {
+        initial_game.jump_to_end();
+    }
+
+    // Games read from stdin have nowhere to come back to
+    let mut resume = match path_arg {
+        _ if view.no_resume => None,
+        Some(playlist::STDIN_ARG) => None,
+        path => ViewerState::default_path().map(|state_path| Resume {
+            state: ViewerState::load(&state_path),
+            state_path,
+            source: PathBuf::from(path.unwrap_or("./sgf")),
+        }),
+    };
+    // A live game always opens at its latest move instead
+    let resume_offer = resume
+        .as_ref()
+        .filter(|_| !view.follow)
+        .and_then(|resume| resume.state.resume_point(&resume.source, playlist.files()))
+        .filter(|&(index, move_number)| index != playlist.current_index() || move_number > 0)
+        .map(|(index, move_number)| ui::ResumeOffer {
+            index,
+            move_number,
+            name: playlist.files()[index]
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            shown_at: Instant::now(),
+        });
 
     // Setup terminal
     enable_raw_mode()?;
@@ -86,7 +240,14 @@ fn main() -> Result<(), io::Error> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run app
-    let res = run_app(&mut terminal, initial_game, playlist);
+    let res = run_app(
+        &mut terminal,
+        initial_game,
+        playlist,
+        &view,
+        resume.as_mut(),
+        resume_offer,
+    );
 
     // Restore terminal
     disable_raw_mode()?;
@@ -100,6 +261,11 @@ fn main() -> Result<(), io::Error> {
     if let Err(err) = res {
         eprintln!("Error: {:?}", err);
     }
+    if let Some(resume) = &resume {
+        if let Err(err) = resume.state.save(&resume.state_path) {
+            eprintln!("Failed to save viewer state: {}", err);
+        }
+    }
 
     Ok(())
 }
@@ -108,12 +274,23 @@ fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     initial_game: game::GameState,
     mut playlist: PlaylistManager,
+    view: &cli::ViewArgs,
+    mut resume: Option<&mut Resume>,
+    resume_offer: Option<ui::ResumeOffer>,
 ) -> io::Result<()> {
+    let (watch, follow) = (view.watch, view.follow);
+    let mut last_refresh = Instant::now();
+    let mut last_follow = Instant::now();
+    let mut follower: Option<FileFollower> = None;
+    // The search stays on when the game changes and applies to the new one
+    let mut search_query: Option<String> = None;
+
     let mut app_state = AppState::Playing {
-        game: initial_game,
+        game: Box::new(initial_game),
         auto_play: true,
         playback_speed: 1,
         last_auto_advance: Instant::now(),
+        overlay: resume_offer.map(ui::Overlay::Resume),
     };
 
     loop {
@@ -123,6 +300,7 @@ fn run_app(
                 auto_play,
                 playback_speed,
                 last_auto_advance,
+                overlay,
             } => {
                 // Calculate delay based on speed
                 let auto_play_delay = std::time::Duration::from_millis(match *playback_speed {
@@ -132,66 +310,115 @@ fn run_app(
                     _ => 3000,
                 });
 
-                terminal.draw(|f| ui::render_game(f, game, *auto_play, *playback_speed))?;
+                let search = search_query
+                    .as_deref()
+                    .map(|query| GameSearch::new(game, query));
 
-                // Auto-play logic
-                if *auto_play && last_auto_advance.elapsed() >= auto_play_delay {
-                    if game.current_move >= game.moves.len() {
-                        // Reached end of current game
-                        if playlist.has_next() {
-                            // Transition to next file
-                            let from_title = game.get_property("GN").unwrap_or("Game").to_string();
-
-                            if let Some(next_path) = playlist.peek_next() {
-                                match load_game_from_path(next_path) {
-                                    Ok(next_game) => {
-                                        let to_title = next_game
-                                            .get_property("GN")
-                                            .unwrap_or("Game")
-                                            .to_string();
-                                        playlist.next();
-
-                                        app_state = AppState::Transition {
-                                            from_title,
-                                            to_title,
-                                            start_time: Instant::now(),
-                                        };
-                                        continue;
-                                    }
-                                    Err(e) => {
-                                        eprintln!("Failed to load next game: {}", e);
-                                        *auto_play = false;
-                                    }
-                                }
+                let frame_area = terminal
+                    .draw(|f| {
+                        ui::render_game(
+                            f,
+                            game,
+                            *auto_play,
+                            *playback_speed,
+                            &playlist,
+                            follow,
+                            search.as_ref(),
+                        );
+                        match overlay {
+                            Some(ui::Overlay::Help) => {
+                                ui::render_help(f, game, *auto_play, *playback_speed, &playlist)
+                            }
+                            Some(ui::Overlay::Info) => {
+                                let entry = playlist.entry(playlist.current_index());
+                                let note = entry.as_ref().and_then(|e| e.note.as_deref());
+                                ui::render_info(f, game, note)
+                            }
+                            Some(ui::Overlay::Browser(browser)) => {
+                                ui::render_browser(f, browser, playlist.current_index())
+                            }
+                            Some(ui::Overlay::BookmarkLabel(prompt)) => {
+                                ui::render_label_prompt(f, prompt)
                             }
-                        } else if game.is_looping_enabled() && !playlist.is_single_file() {
-                            // Last file, loop back to first
-                            let from_title = game.get_property("GN").unwrap_or("Game").to_string();
-                            playlist.reset();
+                            Some(ui::Overlay::Bookmarks(list)) => ui::render_bookmarks(f, list),
+                            Some(ui::Overlay::Pattern(panel)) => ui::render_pattern(f, panel),
+                            Some(ui::Overlay::Search(prompt)) => {
+                                ui::render_search_prompt(f, prompt, game)
+                            }
+                            Some(ui::Overlay::Resume(offer)) => {
+                                let left = RESUME_TIMEOUT.saturating_sub(offer.shown_at.elapsed());
+                                ui::render_resume(f, offer, left.as_secs() + 1)
+                            }
+                            None => {}
+                        }
+                    })?
+                    .area;
+
+                // Re-read a live game file as it grows
+                if follow && last_follow.elapsed() >= FOLLOW_INTERVAL {
+                    if follower
+                        .as_ref()
+                        .is_none_or(|follower| follower.path() != playlist.current())
+                    {
+                        follower = Some(FileFollower::new(playlist.current()));
+                    }
+                    if let Some(tree) = follower.as_mut().and_then(|follower| follower.poll()) {
+                        game.update_from_tree(tree);
+                    }
+                    last_follow = Instant::now();
+                }
+
+                // Remember the position for the next launch (a finished game
+                // starts over), but not while the last one is still on offer
+                if let Some(ui::Overlay::Resume(offer)) = overlay {
+                    if offer.shown_at.elapsed() >= RESUME_TIMEOUT {
+                        *overlay = None;
+                        *last_auto_advance = Instant::now();
+                    }
+                } else if let Some(resume) = resume.as_mut() {
+                    let move_number = if game.current_move >= game.moves.len() {
+                        0
+                    } else {
+                        game.current_move
+                    };
+                    resume.state.set_last_move(playlist.current(), move_number);
+                    resume
+                        .state
+                        .set_last_game(&resume.source, playlist.current());
+                }
 
-                            match load_game_from_path(playlist.current()) {
-                                Ok(first_game) => {
+                // Auto-play logic
+                if *auto_play
+                    && last_auto_advance.elapsed() >= auto_play_delay
+                    && !matches!(overlay, Some(ui::Overlay::Resume(_)))
+                {
+                    if follow && game.current_move >= game.moves.len() {
+                        // A live game isn't over at its last move; wait for the next one
+                    } else if game.current_move >= game.moves.len() {
+                        // Reached end of current game: the repeat mode picks the
+                        // next game, or None to replay (or stop on) this one
+                        match playlist.index_after_end() {
+                            Some(index) => match playlist.load(index) {
+                                Ok(next_game) => {
                                     let to_title =
-                                        first_game.get_property("GN").unwrap_or("Game").to_string();
+                                        next_game.get_property("GN").unwrap_or("Game").to_string();
+                                    playlist.jump_to(index);
 
                                     app_state = AppState::Transition {
-                                        from_title,
                                         to_title,
                                         start_time: Instant::now(),
                                     };
                                     continue;
                                 }
                                 Err(e) => {
-                                    eprintln!("Failed to reload first game: {}", e);
+                                    eprintln!("Failed to load next game: {}", e);
+                                    *auto_play = false;
+                                }
+                            },
+                            None => {
+                                if !game.next() {
                                     *auto_play = false;
                                 }
-                            }
-                        } else {
-                            // Last file + no loop OR single file
-                            // Let game handle its own looping
-                            let can_continue = game.next();
-                            if !can_continue {
-                                *auto_play = false;
                             }
                         }
                     } else {
@@ -201,11 +428,255 @@ fn run_app(
                     *last_auto_advance = Instant::now();
                 }
 
+                // Pick up games added to or deleted from a watched folder. The
+                // browser holds playlist indices, so wait until it is closed
+                if watch
+                    && last_refresh.elapsed() >= WATCH_INTERVAL
+                    && !matches!(overlay, Some(ui::Overlay::Browser(_)))
+                {
+                    // A folder that can't be read right now keeps the old playlist
+                    let _ = playlist.refresh();
+                    last_refresh = Instant::now();
+                }
+
                 // Event handling
                 if event::poll(std::time::Duration::from_millis(100))? {
-                    if let Event::Key(key) = event::read()? {
-                        match key.code {
+                    match event::read()? {
+                        // The resume offer only takes its answer (and quit)
+                        Event::Key(key) if matches!(overlay, Some(ui::Overlay::Resume(_))) => {
+                            if let Some(ui::Overlay::Resume(offer)) = overlay {
+                                let (index, move_number) = (offer.index, offer.move_number);
+                                match key.code {
+                                    KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                                        if let Ok(resumed_game) = switch_game(&mut playlist, index)
+                                        {
+                                            **game = resumed_game;
+                                            game.jump_to_move(move_number);
+                                        }
+                                        *overlay = None;
+                                        *last_auto_advance = Instant::now();
+                                    }
+                                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                                        *overlay = None;
+                                        *last_auto_advance = Instant::now();
+                                    }
+                                    KeyCode::Char('q') => return Ok(()),
+                                    _ => {}
+                                }
+                            }
+                        }
+                        // The label prompt takes all keys so the label can be typed
+                        Event::Key(key)
+                            if matches!(overlay, Some(ui::Overlay::BookmarkLabel(_))) =>
+                        {
+                            if let Some(ui::Overlay::BookmarkLabel(prompt)) = overlay {
+                                match key.code {
+                                    KeyCode::Esc => *overlay = None,
+                                    KeyCode::Backspace => {
+                                        prompt.text.pop();
+                                    }
+                                    KeyCode::Char(ch) => prompt.text.push(ch),
+                                    KeyCode::Enter => {
+                                        let bookmark = Bookmark::new(
+                                            &prompt.path,
+                                            prompt.move_number,
+                                            Some(&prompt.text),
+                                        );
+                                        *overlay = match update_bookmarks(|store| {
+                                            store.add(bookmark)
+                                        }) {
+                                            Ok(_) => None,
+                                            Err(e) => {
+                                                let mut list = ui::BookmarkList::new(Vec::new());
+                                                list.set_error(format!(
+                                                    "Failed to save bookmark: {}",
+                                                    e
+                                                ));
+                                                Some(ui::Overlay::Bookmarks(list))
+                                            }
+                                        };
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        Event::Key(key) if matches!(overlay, Some(ui::Overlay::Bookmarks(_))) => {
+                            if let Some(ui::Overlay::Bookmarks(list)) = overlay {
+                                match key.code {
+                                    KeyCode::Esc => *overlay = None,
+                                    KeyCode::Char('q') => return Ok(()),
+                                    KeyCode::Up => list.select_previous(),
+                                    KeyCode::Down => list.select_next(),
+                                    KeyCode::Enter => {
+                                        if let Some(bookmark) = list.selected().cloned() {
+                                            match open_bookmark(&mut playlist, &bookmark) {
+                                                Ok(bookmarked_game) => {
+                                                    **game = bookmarked_game;
+                                                    *auto_play = false;
+                                                    *overlay = None;
+                                                }
+                                                Err(e) => list.set_error(e.to_string()),
+                                            }
+                                        }
+                                    }
+                                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                                        if let Some(bookmark) = list.selected().cloned() {
+                                            match update_bookmarks(|store| {
+                                                store.remove(&bookmark.path, bookmark.move_number);
+                                            }) {
+                                                Ok(bookmarks) => list.set_bookmarks(bookmarks),
+                                                Err(e) => list.set_error(e.to_string()),
+                                            }
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        // The search prompt takes all keys so the query can be typed
+                        Event::Key(key) if matches!(overlay, Some(ui::Overlay::Search(_))) => {
+                            if let Some(ui::Overlay::Search(prompt)) = overlay {
+                                match key.code {
+                                    KeyCode::Esc => *overlay = None,
+                                    KeyCode::Backspace => {
+                                        prompt.text.pop();
+                                    }
+                                    KeyCode::Char(ch) => prompt.text.push(ch),
+                                    KeyCode::Enter => {
+                                        let query = prompt.text.trim().to_string();
+                                        search_query = None;
+                                        if !query.is_empty() {
+                                            // Go to the first match from here on, unless
+                                            // this move already matches
+                                            let search = GameSearch::new(game, &query);
+                                            if search.at(game.current_move).is_none() {
+                                                if let Some(next) =
+                                                    search.next_after(game.current_move)
+                                                {
+                                                    game.jump_to_move(next);
+                                                }
+                                            }
+                                            *auto_play = false;
+                                            search_query = Some(query);
+                                        }
+                                        *overlay = None;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        Event::Key(key) if matches!(overlay, Some(ui::Overlay::Pattern(_))) => {
+                            if let Some(ui::Overlay::Pattern(panel)) = overlay {
+                                match key.code {
+                                    KeyCode::Esc => *overlay = None,
+                                    KeyCode::Char('q') => return Ok(()),
+                                    KeyCode::Tab => panel.next_corner(),
+                                    KeyCode::Char('+') | KeyCode::Char('=') => panel.grow(),
+                                    KeyCode::Char('-') => panel.shrink(),
+                                    KeyCode::Enter => {
+                                        let pattern = panel.pattern();
+                                        if pattern.stones() == 0 {
+                                            panel.set_error(
+                                                "No stones in this corner to look for".to_string(),
+                                            );
+                                        } else {
+                                            panel.set_search(PatternSearch::run(
+                                                &pattern, &playlist,
+                                            ));
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        // The file browser takes all keys so the filter can be typed
+                        Event::Key(key) if matches!(overlay, Some(ui::Overlay::Browser(_))) => {
+                            if let Some(ui::Overlay::Browser(browser)) = overlay {
+                                match key.code {
+                                    KeyCode::Esc => *overlay = None,
+                                    KeyCode::Up => browser.select_previous(),
+                                    KeyCode::Down => browser.select_next(),
+                                    KeyCode::Backspace => browser.pop_filter(),
+                                    KeyCode::Char(ch) => browser.push_filter(ch),
+                                    KeyCode::Enter => {
+                                        if let Some(index) = browser.selected_index() {
+                                            match switch_game(&mut playlist, index) {
+                                                Ok(selected_game) => {
+                                                    **game = selected_game;
+                                                    *last_auto_advance = Instant::now();
+                                                    *overlay = None;
+                                                }
+                                                Err(e) => browser.set_error(e.to_string()),
+                                            }
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        // While an overlay is open, only overlay and quit keys apply
+                        Event::Key(key) if overlay.is_some() => match key.code {
+                            KeyCode::Esc => *overlay = None,
+                            KeyCode::Char('q') => return Ok(()),
+                            KeyCode::Char('?') => ui::Overlay::toggle(overlay, ui::Overlay::Help),
+                            KeyCode::Char('i') | KeyCode::Char('I') => {
+                                ui::Overlay::toggle(overlay, ui::Overlay::Info)
+                            }
+                            _ => {}
+                        },
+                        Event::Key(key) => match key.code {
+                            KeyCode::Esc if search_query.is_some() => search_query = None,
                             KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Char('j') | KeyCode::Char('J') => {
+                                *overlay = Some(ui::Overlay::Pattern(Box::new(
+                                    ui::PatternPanel::new(game),
+                                )))
+                            }
+                            KeyCode::Char('/') => {
+                                *overlay = Some(ui::Overlay::Search(ui::SearchPrompt {
+                                    text: search_query.clone().unwrap_or_default(),
+                                }))
+                            }
+                            // While searching, n/N move between matches instead of games
+                            KeyCode::Char(ch @ ('n' | 'N')) if search.is_some() => {
+                                let target = search.as_ref().and_then(|search| {
+                                    if ch == 'n' {
+                                        search.next_after(game.current_move)
+                                    } else {
+                                        search.previous_before(game.current_move)
+                                    }
+                                });
+                                if let Some(move_number) = target {
+                                    *auto_play = false;
+                                    game.jump_to_move(move_number);
+                                }
+                            }
+                            KeyCode::Char('?') => *overlay = Some(ui::Overlay::Help),
+                            KeyCode::Char('i') | KeyCode::Char('I') => {
+                                *overlay = Some(ui::Overlay::Info)
+                            }
+                            KeyCode::Char('o') | KeyCode::Char('O') => {
+                                *overlay =
+                                    Some(ui::Overlay::Browser(ui::FileBrowser::new(&playlist)))
+                            }
+                            // Games read from stdin have no file to come back to
+                            KeyCode::Char('m') | KeyCode::Char('M')
+                                if !playlist.is_in_memory(playlist.current()) =>
+                            {
+                                *overlay = Some(ui::Overlay::BookmarkLabel(ui::LabelPrompt {
+                                    path: playlist.current().to_path_buf(),
+                                    move_number: game.current_move,
+                                    text: String::new(),
+                                }));
+                            }
+                            KeyCode::Char('b') | KeyCode::Char('B') => {
+                                let mut list = ui::BookmarkList::new(Vec::new());
+                                match load_bookmarks() {
+                                    Ok(bookmarks) => list.set_bookmarks(bookmarks),
+                                    Err(e) => list.set_error(e.to_string()),
+                                }
+                                *overlay = Some(ui::Overlay::Bookmarks(list));
+                            }
                             KeyCode::Char(' ') => {
                                 *auto_play = !*auto_play;
                                 *last_auto_advance = Instant::now();
@@ -226,18 +697,81 @@ fn run_app(
                                 *auto_play = false;
                                 game.jump_to_end();
                             }
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::PageDown => {
+                                if let Some(Ok(next_game)) = playlist
+                                    .next_index()
+                                    .map(|index| switch_game(&mut playlist, index))
+                                {
+                                    **game = next_game;
+                                    *last_auto_advance = Instant::now();
+                                }
+                            }
+                            KeyCode::Char('p') | KeyCode::Char('P') | KeyCode::PageUp => {
+                                if let Some(Ok(previous_game)) = playlist
+                                    .previous_index()
+                                    .map(|index| switch_game(&mut playlist, index))
+                                {
+                                    **game = previous_game;
+                                    *last_auto_advance = Instant::now();
+                                }
+                            }
                             KeyCode::Char('l') | KeyCode::Char('L') => {
-                                game.toggle_looping();
+                                cycle_repeat(&mut playlist, game);
+                            }
+                            KeyCode::Char('r') | KeyCode::Char('R') => {
+                                toggle_shuffle(&mut playlist);
                             }
                             KeyCode::Char('s') | KeyCode::Char('S') => {
-                                *playback_speed = if *playback_speed >= 3 {
-                                    1
-                                } else {
-                                    *playback_speed + 1
-                                };
+                                *playback_speed = next_playback_speed(*playback_speed);
                             }
                             _ => {}
-                        }
+                        },
+                        Event::Mouse(mouse) if overlay.is_none() => match mouse.kind {
+                            MouseEventKind::ScrollUp => {
+                                *auto_play = false;
+                                game.previous();
+                            }
+                            MouseEventKind::ScrollDown => {
+                                *auto_play = false;
+                                game.next();
+                            }
+                            MouseEventKind::Down(MouseButton::Left) => {
+                                if let Some(control) = ui::status_control_at(
+                                    frame_area,
+                                    game,
+                                    *auto_play,
+                                    *playback_speed,
+                                    &playlist,
+                                    mouse.column,
+                                    mouse.row,
+                                ) {
+                                    match control {
+                                        ui::StatusControl::PlayPause => {
+                                            *auto_play = !*auto_play;
+                                            *last_auto_advance = Instant::now();
+                                        }
+                                        ui::StatusControl::Loop => {
+                                            cycle_repeat(&mut playlist, game)
+                                        }
+                                        ui::StatusControl::Speed => {
+                                            *playback_speed = next_playback_speed(*playback_speed);
+                                        }
+                                    }
+                                } else if let Some((row, col)) =
+                                    ui::board_point_at(frame_area, game, mouse.column, mouse.row)
+                                {
+                                    // Clicking a stone jumps to the move that placed it
+                                    if let Some(move_number) = game.move_number_at(row, col) {
+                                        *auto_play = false;
+                                        game.jump_to_move(move_number);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        },
+                        // Re-layout immediately instead of waiting for the next draw
+                        Event::Resize(_, _) => terminal.autoresize()?,
+                        _ => {}
                     }
                 }
             }
@@ -261,13 +795,14 @@ fn run_app(
 
                 // After 3 seconds, load next game
                 if elapsed >= std::time::Duration::from_secs(3) {
-                    match load_game_from_path(playlist.current()) {
+                    match load_game(&playlist, playlist.current_index()) {
                         Ok(next_game) => {
                             app_state = AppState::Playing {
-                                game: next_game,
+                                game: Box::new(next_game),
                                 auto_play: true,
                                 playback_speed: 1,
                                 last_auto_advance: Instant::now(),
+                                overlay: None,
                             };
                         }
                         Err(e) => {
@@ -279,6 +814,4 @@ fn run_app(
             }
         }
     }
-
-    Ok(())
 }
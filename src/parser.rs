@@ -1,16 +1,48 @@
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Color {
+    #[default]
     Black,
     White,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub color: Color,
     pub position: Option<(u8, u8)>, // None for pass
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub comment: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub labels: Vec<Label>,
+    // Other moves played instead of this one, each followed by its own line
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub variations: Vec<Vec<Move>>,
+}
+
+// Text marked on an intersection (SGF `LB[dd:A]`)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Label {
+    pub position: (u8, u8),
+    pub text: String,
 }
 
 #[derive(Debug)]
@@ -19,6 +51,63 @@ pub struct GameTree {
     pub moves: Vec<Move>,
 }
 
+impl GameTree {
+    // Board size from SZ, 19x19 when there is none. Coordinates only reach
+    // 19 lines, so larger boards (and SZ[0]) are rejected
+    pub fn board_size(&self) -> Result<u8, ParseError> {
+        match self.properties.get("SZ").and_then(|v| v.first()) {
+            Some(value) => value
+                .trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|size| (1..=19).contains(size))
+                .ok_or_else(|| ParseError::InvalidFormat(format!("Invalid board size: {}", value))),
+            None => Ok(19),
+        }
+    }
+
+    // Reject a game whose size is invalid or whose setup stones or moves, in
+    // any variation, fall outside the board
+    pub fn check_board(&self) -> Result<(), ParseError> {
+        let size = self.board_size()?;
+        let off_board = |what: String, (row, col): (u8, u8)| {
+            ParseError::InvalidFormat(format!(
+                "{} at ({}, {}) is off the {}x{} board",
+                what, row, col, size, size
+            ))
+        };
+
+        for key in ["AB", "AW"] {
+            let values = self.properties.get(key).into_iter().flatten();
+            for point in values.flat_map(|value| parse_point_list(value)) {
+                if point.0 >= size || point.1 >= size {
+                    return Err(off_board("Setup stone".to_string(), point));
+                }
+            }
+        }
+        check_line(&self.moves, size, &off_board)
+    }
+}
+
+// Moves in a line and its variations, numbered from the start of the line
+fn check_line(
+    moves: &[Move],
+    size: u8,
+    off_board: &impl Fn(String, (u8, u8)) -> ParseError,
+) -> Result<(), ParseError> {
+    for (index, mv) in moves.iter().enumerate() {
+        if let Some((row, col)) = mv.position {
+            if row >= size || col >= size {
+                return Err(off_board(format!("Move {}", index + 1), (row, col)));
+            }
+        }
+        for line in &mv.variations {
+            check_line(line, size, off_board)?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub enum ParseError {
     InvalidFormat(String),
@@ -48,6 +137,36 @@ fn sgf_to_coords(s: &str) -> Option<(u8, u8)> {
     }
 }
 
+// Board position to SGF coordinates, e.g. (3, 15) -> "pd"
+pub(crate) fn coords_to_sgf((row, col): (u8, u8)) -> String {
+    [(b'a' + col) as char, (b'a' + row) as char]
+        .iter()
+        .collect()
+}
+
+// Parse a point list value: one point "dd", or a rectangle "dd:ff" given by
+// two opposite corners
+pub(crate) fn parse_point_list(value: &str) -> Vec<(u8, u8)> {
+    let Some((from, to)) = value.split_once(':') else {
+        return sgf_to_coords(value).into_iter().collect();
+    };
+    let (Some(a), Some(b)) = (sgf_to_coords(from), sgf_to_coords(to)) else {
+        return Vec::new();
+    };
+    (a.0.min(b.0)..=a.0.max(b.0))
+        .flat_map(|row| (a.1.min(b.1)..=a.1.max(b.1)).map(move |col| (row, col)))
+        .collect()
+}
+
+// Parse a label value "dd:text"
+pub(crate) fn parse_label(value: &str) -> Option<Label> {
+    let (point, text) = value.split_once(':')?;
+    Some(Label {
+        position: sgf_to_coords(point)?,
+        text: text.to_string(),
+    })
+}
+
 pub fn parse_sgf(input: &str) -> Result<GameTree, ParseError> {
     let input = input.trim();
 
@@ -58,7 +177,7 @@ pub fn parse_sgf(input: &str) -> Result<GameTree, ParseError> {
     }
 
     // Remove outer parentheses
-    let content = &input[1..input.len() - 1];
+    let content = input[1..input.len() - 1].trim_start();
 
     if !content.starts_with(';') {
         return Err(ParseError::InvalidFormat(
@@ -67,77 +186,186 @@ pub fn parse_sgf(input: &str) -> Result<GameTree, ParseError> {
     }
 
     let mut properties = HashMap::new();
-    let mut moves = Vec::new();
-
-    // Split by semicolons to get nodes
-    let nodes: Vec<&str> = content.split(';').filter(|s| !s.is_empty()).collect();
-
-    for (idx, node) in nodes.iter().enumerate() {
-        let mut chars = node.chars().peekable();
-
-        while let Some(ch) = chars.next() {
-            if ch.is_ascii_uppercase() {
-                // Found a property key
-                let mut key = String::new();
-                key.push(ch);
-
-                // Read rest of key
-                while let Some(&next_ch) = chars.peek() {
-                    if next_ch.is_ascii_uppercase() {
-                        key.push(chars.next().unwrap());
-                    } else {
-                        break;
-                    }
-                }
+    let moves = parse_line(&mut content.chars().peekable(), Some(&mut properties));
 
-                // Read value(s) in brackets
-                let mut values = Vec::new();
-                while let Some(&next_ch) = chars.peek() {
-                    if next_ch == '[' {
-                        chars.next(); // consume '['
-                        let mut value = String::new();
-
-                        while let Some(val_ch) = chars.next() {
-                            if val_ch == ']' {
-                                break;
-                            }
-                            value.push(val_ch);
-                        }
+    Ok(GameTree { properties, moves })
+}
 
-                        values.push(value);
-                    } else {
-                        break;
-                    }
-                }
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+// One line of play: its nodes up to the closing ')', then the variations that
+// branch from its last node. The first variation continues the line and the
+// others are kept on its first move. The root node's properties go to `root`
+fn parse_line(chars: &mut Chars, mut root: Option<&mut HashMap<String, Vec<String>>>) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::new();
+    let mut branches: Vec<Vec<Move>> = Vec::new();
+    let mut first_node = true;
+    // A comment from a node without a move, before any move in a variation
+    let mut carried_comment = None;
 
-                if !values.is_empty() {
-                    // Check if this is a move property
+    while let Some(ch) = chars.next() {
+        match ch {
+            ';' => {
+                let is_root = root.is_some() && first_node;
+                first_node = false;
+                let moves_before = moves.len();
+                let mut node_comment = None;
+                let mut node_name = None;
+                let mut node_labels = Vec::new();
+
+                for (key, values) in parse_node(chars) {
                     match key.as_str() {
-                        "B" => {
-                            moves.push(Move {
-                                color: Color::Black,
-                                position: sgf_to_coords(&values[0]),
-                                comment: None,
-                            });
-                        }
-                        "W" => {
-                            moves.push(Move {
-                                color: Color::White,
-                                position: sgf_to_coords(&values[0]),
-                                comment: None,
-                            });
+                        "B" | "W" => moves.push(Move {
+                            color: if key == "B" {
+                                Color::Black
+                            } else {
+                                Color::White
+                            },
+                            position: sgf_to_coords(&values[0]),
+                            ..Default::default()
+                        }),
+                        // Comments, names and labels on move nodes belong to that move
+                        "C" if !is_root => node_comment = Some(values[0].clone()),
+                        "N" if !is_root => node_name = Some(values[0].clone()),
+                        "LB" if !is_root => {
+                            node_labels.extend(values.iter().filter_map(|value| parse_label(value)))
                         }
                         _ => {
-                            // Store as property (only for first node - root properties)
-                            if idx == 0 {
+                            // Store as property (only for the root node)
+                            if let Some(properties) = root.as_deref_mut().filter(|_| is_root) {
                                 properties.insert(key, values);
                             }
                         }
                     }
                 }
+
+                // The comment may come before or after the move in the node
+                if moves.len() > moves_before {
+                    if let Some(mv) = moves.last_mut() {
+                        mv.comment = carried_comment.take();
+                        if let Some(comment) = node_comment {
+                            append_comment(&mut mv.comment, comment);
+                        }
+                        mv.name = node_name;
+                        mv.labels = node_labels;
+                    }
+                } else if let Some(comment) = node_comment {
+                    // A node without a move (e.g. setup stones): keep its comment with
+                    // the move before it, or with the game comment before the first move
+                    match (moves.last_mut(), root.as_deref_mut()) {
+                        (Some(mv), _) => append_comment(&mut mv.comment, comment),
+                        (None, Some(properties)) => {
+                            let game_comment = properties.entry("C".to_string()).or_default();
+                            let mut text = game_comment.pop();
+                            append_comment(&mut text, comment);
+                            game_comment.extend(text);
+                        }
+                        (None, None) => append_comment(&mut carried_comment, comment),
+                    }
+                }
             }
+            '(' => branches.push(parse_line(chars, None)),
+            ')' => break,
+            _ => {}
         }
     }
 
-    Ok(GameTree { properties, moves })
+    let mut branches = branches.into_iter().filter(|line| !line.is_empty());
+    if let Some(mut main) = branches.next() {
+        main[0].variations.extend(branches);
+        moves.extend(main);
+    }
+    moves
+}
+
+// The properties of one node, up to the next node or variation. Property
+// values may escape ']' with a backslash; lowercase letters in identifiers
+// (old FF[3] files) are skipped
+fn parse_node(chars: &mut Chars) -> Vec<(String, Vec<String>)> {
+    let mut properties = Vec::new();
+    let mut key = String::new();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ';' | '(' | ')' => break,
+            '[' => {
+                let mut values = Vec::new();
+                while chars.peek() == Some(&'[') {
+                    chars.next(); // consume '['
+                    let mut value = String::new();
+                    while let Some(val_ch) = chars.next() {
+                        match val_ch {
+                            ']' => break,
+                            '\\' => value.extend(chars.next()),
+                            _ => value.push(val_ch),
+                        }
+                    }
+                    values.push(value);
+                    while chars.peek().is_some_and(|ch| ch.is_whitespace()) {
+                        chars.next();
+                    }
+                }
+                if !key.is_empty() {
+                    properties.push((std::mem::take(&mut key), values));
+                }
+            }
+            _ => {
+                chars.next();
+                if ch.is_ascii_uppercase() {
+                    key.push(ch);
+                } else if !ch.is_ascii_lowercase() && !ch.is_whitespace() {
+                    key.clear();
+                }
+            }
+        }
+    }
+
+    properties
+}
+
+fn append_comment(existing: &mut Option<String>, comment: String) {
+    *existing = Some(match existing.take() {
+        Some(text) => format!("{}\n\n{}", text, comment),
+        None => comment,
+    });
+}
+
+// Split an SGF collection "(;...)(;...)" into its top-level game trees.
+// Brackets are tracked so parentheses inside property values don't count
+pub fn split_collection(input: &str) -> Vec<&str> {
+    let mut games = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut in_value = false;
+    let mut escaped = false;
+
+    for (idx, ch) in input.char_indices() {
+        if in_value {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                ']' => in_value = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '[' => in_value = true,
+            '(' => {
+                if depth == 0 {
+                    start = idx;
+                }
+                depth += 1;
+            }
+            ')' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    games.push(&input[start..=idx]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    games
 }
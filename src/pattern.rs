@@ -0,0 +1,416 @@
+// Local pattern search, e.g. for joseki: a rectangle of one board is looked
+// for in every position of other games, in all 8 symmetries and with colours
+// swapped. Each time the pattern appears, the next move played inside the
+// region is its continuation.
+//
+// A pattern matches when every point of the region holds the same stone, or
+// is empty, as in the pattern. Continuations are reported in the pattern's own
+// orientation and colours.
+
+use crate::export::{column_label, row_label};
+use crate::game::{Board, GameState};
+use crate::parser::Color;
+use crate::playlist::PlaylistManager;
+use crate::position::{transform, SYMMETRIES};
+use std::path::{Path, PathBuf};
+
+// Corner regions are this many lines wide unless chosen otherwise
+pub const CORNER_EXTENT: u8 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+impl Corner {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "top-left" | "tl" => Some(Corner::TopLeft),
+            "top-right" | "tr" => Some(Corner::TopRight),
+            "bottom-right" | "br" => Some(Corner::BottomRight),
+            "bottom-left" | "bl" => Some(Corner::BottomLeft),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Corner::TopLeft => "Top-left",
+            Corner::TopRight => "Top-right",
+            Corner::BottomRight => "Bottom-right",
+            Corner::BottomLeft => "Bottom-left",
+        }
+    }
+
+    // Clockwise
+    pub fn next(&self) -> Self {
+        match self {
+            Corner::TopLeft => Corner::TopRight,
+            Corner::TopRight => Corner::BottomRight,
+            Corner::BottomRight => Corner::BottomLeft,
+            Corner::BottomLeft => Corner::TopLeft,
+        }
+    }
+}
+
+// A rectangle of board points, edges included
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    pub top: u8,
+    pub left: u8,
+    pub bottom: u8,
+    pub right: u8,
+}
+
+impl Region {
+    // The rectangle between two opposite corner points, in either order
+    pub fn spanning(a: (u8, u8), b: (u8, u8)) -> Self {
+        Region {
+            top: a.0.min(b.0),
+            left: a.1.min(b.1),
+            bottom: a.0.max(b.0),
+            right: a.1.max(b.1),
+        }
+    }
+
+    // `extent` lines from each edge at a corner, limited to the board
+    pub fn corner(corner: Corner, extent: u8, size: u8) -> Self {
+        let extent = extent.clamp(1, size);
+        let (near, far) = (0, size - 1);
+        let (top, bottom) = match corner {
+            Corner::TopLeft | Corner::TopRight => (near, extent - 1),
+            Corner::BottomLeft | Corner::BottomRight => (size - extent, far),
+        };
+        let (left, right) = match corner {
+            Corner::TopLeft | Corner::BottomLeft => (near, extent - 1),
+            Corner::TopRight | Corner::BottomRight => (size - extent, far),
+        };
+        Region {
+            top,
+            left,
+            bottom,
+            right,
+        }
+    }
+
+    // Two corners in diagram coordinates, e.g. "A19:G13", or a corner name
+    // such as "top-left"
+    pub fn parse(text: &str, size: u8) -> Result<Self, String> {
+        if let Some(corner) = Corner::from_name(text) {
+            return Ok(Region::corner(corner, CORNER_EXTENT, size));
+        }
+        let invalid = || {
+            format!(
+                "Invalid region: {} (expected e.g. A19:G13 or top-left)",
+                text
+            )
+        };
+        let (a, b) = text.split_once(':').ok_or_else(invalid)?;
+        let a = parse_point(a, size).ok_or_else(invalid)?;
+        let b = parse_point(b, size).ok_or_else(invalid)?;
+        Ok(Region::spanning(a, b))
+    }
+
+    pub fn contains(&self, (row, col): (u8, u8)) -> bool {
+        (self.top..=self.bottom).contains(&row) && (self.left..=self.right).contains(&col)
+    }
+
+    pub fn width(&self) -> u8 {
+        self.right - self.left + 1
+    }
+
+    pub fn height(&self) -> u8 {
+        self.bottom - self.top + 1
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        (self.top..=self.bottom)
+            .flat_map(move |row| (self.left..=self.right).map(move |col| (row, col)))
+    }
+
+    // "A19:G13": top-left and bottom-right corners
+    pub fn name(&self, size: u8) -> String {
+        format!(
+            "{}:{}",
+            point_name((self.top, self.left), size),
+            point_name((self.bottom, self.right), size)
+        )
+    }
+}
+
+// Diagram coordinates of a point, e.g. "D16"
+pub fn point_name((row, col): (u8, u8), size: u8) -> String {
+    format!("{}{}", column_label(col), row_label(row, size))
+}
+
+// "D16" back to a board point; column letters skip 'I'
+fn parse_point(text: &str, size: u8) -> Option<(u8, u8)> {
+    let text = text.trim().to_uppercase();
+    let mut chars = text.chars();
+    let letter = chars
+        .next()
+        .filter(|ch| ch.is_ascii_uppercase() && *ch != 'I')? as u8;
+    let col = if letter > b'I' {
+        letter - b'A' - 1
+    } else {
+        letter - b'A'
+    };
+    let number: u8 = chars.as_str().parse().ok()?;
+    if col >= size || number == 0 || number > size {
+        return None;
+    }
+    Some((size - number, col))
+}
+
+// The next move played inside a pattern's region after it appeared
+#[derive(Debug, Clone, PartialEq)]
+pub struct Continuation {
+    pub position: (u8, u8), // In the pattern's orientation
+    pub color: Color,       // In the pattern's colours
+}
+
+// One appearance of a pattern in a game
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternHit {
+    pub path: PathBuf,
+    pub move_number: usize, // The move that completed the pattern
+    pub continuation: Option<Continuation>, // None if the region wasn't played again
+}
+
+// The contents of a region of one board
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    size: u8,
+    region: Region,
+    points: Vec<((u8, u8), Option<Color>)>,
+}
+
+// Points a pattern covers once placed on the board, with Some(true) for black
+// stones, Some(false) for white and None for empty points
+type Placed = Vec<((u8, u8), Option<bool>)>;
+
+// Where a pattern can appear on the board: one symmetry, with or without
+// colours swapped
+struct Placement {
+    symmetry: u8,
+    swapped: bool,
+    frame: Vec<Option<(u8, u8)>>, // Pattern point for each board point the region covers
+    matched: bool,
+    pending: Option<usize>, // Hit still waiting for its continuation
+}
+
+impl Pattern {
+    pub fn from_board(board: &Board, region: Region) -> Self {
+        Pattern {
+            size: board.size,
+            region,
+            points: region
+                .points()
+                .map(|(row, col)| ((row, col), board.get(row, col)))
+                .collect(),
+        }
+    }
+
+    pub fn size(&self) -> u8 {
+        self.size
+    }
+
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn stones(&self) -> usize {
+        self.points
+            .iter()
+            .filter(|(_, stone)| stone.is_some())
+            .count()
+    }
+
+    pub fn get(&self, point: (u8, u8)) -> Option<Color> {
+        self.points
+            .iter()
+            .find(|(at, _)| *at == point)
+            .and_then(|(_, stone)| stone.clone())
+    }
+
+    // Every appearance of the pattern in a game, in move order. Games on a
+    // different board size never match, and neither does a pattern without stones
+    pub fn find(&self, path: &Path, game: GameState) -> Vec<PatternHit> {
+        let mut hits = Vec::new();
+        if game.board.size != self.size || self.stones() == 0 {
+            return hits;
+        }
+
+        let mut placements = self.placements();
+        let mut board = game.start_board();
+        for (index, mv) in game.moves.iter().enumerate() {
+            let Some(point) = mv.position else {
+                continue;
+            };
+            let captured = board.play(point.0, point.1, mv.color.clone());
+            let at = |(row, col): (u8, u8)| row as usize * self.size as usize + col as usize;
+
+            // Only a move or capture inside a placement's region can change
+            // whether it matches
+            for placement in &mut placements {
+                let played = placement.frame[at(point)];
+                if played.is_none() && captured.iter().all(|&p| placement.frame[at(p)].is_none()) {
+                    continue;
+                }
+                if let (Some(position), Some(hit)) = (played, placement.pending) {
+                    let color = if placement.swapped {
+                        swap(&mv.color)
+                    } else {
+                        mv.color.clone()
+                    };
+                    hits[hit].continuation = Some(Continuation { position, color });
+                    placement.pending = None;
+                }
+
+                let matched = self.matches(&board, placement);
+                if matched && !placement.matched {
+                    placement.pending = Some(hits.len());
+                    hits.push(PatternHit {
+                        path: path.to_path_buf(),
+                        move_number: index + 1,
+                        continuation: None,
+                    });
+                } else if !matched {
+                    // Captured out of shape before anyone played there again
+                    placement.pending = None;
+                }
+                placement.matched = matched;
+            }
+        }
+        hits
+    }
+
+    fn matches(&self, board: &Board, placement: &Placement) -> bool {
+        self.points.iter().all(|(point, stone)| {
+            let (row, col) = transform(placement.symmetry, self.size, *point);
+            let expected = match stone {
+                Some(color) if placement.swapped => Some(swap(color)),
+                other => other.clone(),
+            };
+            board.get(row, col) == expected
+        })
+    }
+
+    // The distinct ways the pattern can sit on the board; a symmetric pattern
+    // has fewer than 16
+    fn placements(&self) -> Vec<Placement> {
+        let mut placements: Vec<Placement> = Vec::new();
+        let mut seen: Vec<Placed> = Vec::new();
+
+        for symmetry in 0..SYMMETRIES {
+            for swapped in [false, true] {
+                let mut placed: Placed = self
+                    .points
+                    .iter()
+                    .map(|(point, stone)| {
+                        let black = stone
+                            .as_ref()
+                            .map(|color| (*color == Color::Black) != swapped);
+                        (transform(symmetry, self.size, *point), black)
+                    })
+                    .collect();
+                placed.sort();
+                if seen.contains(&placed) {
+                    continue;
+                }
+                seen.push(placed);
+
+                let mut frame = vec![None; self.size as usize * self.size as usize];
+                for (point, _) in &self.points {
+                    let (row, col) = transform(symmetry, self.size, *point);
+                    frame[row as usize * self.size as usize + col as usize] = Some(*point);
+                }
+                placements.push(Placement {
+                    symmetry,
+                    swapped,
+                    frame,
+                    matched: false,
+                    pending: None,
+                });
+            }
+        }
+        placements
+    }
+}
+
+fn swap(color: &Color) -> Color {
+    match color {
+        Color::Black => Color::White,
+        Color::White => Color::Black,
+    }
+}
+
+// The appearances of a pattern across a playlist
+pub struct PatternSearch {
+    hits: Vec<PatternHit>,
+    games: usize,
+    unreadable: Vec<PathBuf>,
+}
+
+impl PatternSearch {
+    // Search every game in the playlist; games that fail to load are listed
+    // in `unreadable`
+    pub fn run(pattern: &Pattern, playlist: &PlaylistManager) -> Self {
+        let mut search = PatternSearch {
+            hits: Vec::new(),
+            games: 0,
+            unreadable: Vec::new(),
+        };
+        for (index, path) in playlist.files().iter().enumerate() {
+            match playlist.load(index) {
+                Ok(game) => {
+                    search.games += 1;
+                    search.hits.extend(pattern.find(path, game));
+                }
+                Err(_) => search.unreadable.push(path.clone()),
+            }
+        }
+        search
+    }
+
+    pub fn hits(&self) -> &[PatternHit] {
+        &self.hits
+    }
+
+    // Games searched
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    // Games with at least one appearance
+    pub fn matching_games(&self) -> usize {
+        let mut paths: Vec<&Path> = self.hits.iter().map(|hit| hit.path.as_path()).collect();
+        paths.dedup();
+        paths.len()
+    }
+
+    pub fn unreadable(&self) -> &[PathBuf] {
+        &self.unreadable
+    }
+
+    // Continuations with how often each was played, most frequent first
+    // (ties in the order they were first seen). None counts appearances
+    // after which the region wasn't played again
+    pub fn continuations(&self) -> Vec<(Option<Continuation>, usize)> {
+        let mut counts: Vec<(Option<Continuation>, usize)> = Vec::new();
+        for hit in &self.hits {
+            match counts
+                .iter_mut()
+                .find(|(continuation, _)| *continuation == hit.continuation)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((hit.continuation.clone(), 1)),
+            }
+        }
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+    }
+}
@@ -1,21 +1,338 @@
+use crate::archive::{self, ArchiveCache};
+use crate::game::GameState;
+use crate::import;
+use crate::parser;
+use crate::playlist_file::{self, PlaylistEntry};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Path argument that reads the playlist from stdin
+pub const STDIN_ARG: &str = "-";
+
+// Headline metadata for a playlist entry, used by the file browser
+#[derive(Debug, Clone, Default)]
+pub struct GameSummary {
+    pub path: PathBuf,
+    pub black: Option<String>,
+    pub white: Option<String>,
+    pub date: Option<String>,
+    pub result: Option<String>,
+    pub event: Option<String>,
+}
+
+impl GameSummary {
+    // Files that can't be read or parsed still get a summary, just without metadata
+    pub fn load(path: &Path) -> Self {
+        Self::load_cached(path, &mut ArchiveCache::default())
+    }
+
+    fn load_cached(path: &Path, archives: &mut ArchiveCache) -> Self {
+        let properties = import::read_game_tree_cached(path, archives)
+            .ok()
+            .map(|tree| tree.properties)
+            .unwrap_or_default();
+        Self::from_properties(path, &properties)
+    }
+
+    fn from_properties(path: &Path, properties: &HashMap<String, Vec<String>>) -> Self {
+        let get = |key: &str| properties.get(key).and_then(|v| v.first()).cloned();
+
+        GameSummary {
+            path: path.to_path_buf(),
+            black: get("PB"),
+            white: get("PW"),
+            date: get("DT"),
+            result: get("RE"),
+            event: get("EV"),
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    // Case-insensitive substring match against file name, players, date, result and event
+    pub fn matches(&self, filter: &str) -> bool {
+        if filter.is_empty() {
+            return true;
+        }
+
+        let filter = filter.to_lowercase();
+        let file_name = self.file_name();
+        let mut fields = [
+            Some(&file_name),
+            self.black.as_ref(),
+            self.white.as_ref(),
+            self.date.as_ref(),
+            self.result.as_ref(),
+            self.event.as_ref(),
+        ]
+        .into_iter()
+        .flatten();
+        fields.any(|field| field.to_lowercase().contains(&filter))
+    }
+}
+
+// Builds a playlist from game metadata. Text fields are case-insensitive
+// substring matches; every field that is set must match
+#[derive(Debug, Clone, Default)]
+pub struct GameQuery {
+    pub player: Option<String>, // Black or white player (PB/PW)
+    pub event: Option<String>,  // EV
+    pub date: Option<String>,   // DT prefix ("2016", "2016-03") or inclusive range ("2015..2017")
+    pub result: Option<String>, // RE, e.g. "W+" for white wins or "+R" for resignations
+}
+
+impl GameQuery {
+    pub fn is_empty(&self) -> bool {
+        self.player.is_none()
+            && self.event.is_none()
+            && self.date.is_none()
+            && self.result.is_none()
+    }
+
+    pub fn matches(&self, summary: &GameSummary) -> bool {
+        let contains = |field: &Option<String>, wanted: &str| {
+            field
+                .as_deref()
+                .is_some_and(|value| value.to_lowercase().contains(&wanted.to_lowercase()))
+        };
+
+        if let Some(player) = &self.player {
+            if !contains(&summary.black, player) && !contains(&summary.white, player) {
+                return false;
+            }
+        }
+        if let Some(event) = &self.event {
+            if !contains(&summary.event, event) {
+                return false;
+            }
+        }
+        if let Some(result) = &self.result {
+            if !contains(&summary.result, result) {
+                return false;
+            }
+        }
+        if let Some(date) = &self.date {
+            if !date_matches(summary.date.as_deref(), date) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// Compare only as many characters as the query gives, so "2016" covers the year
+fn date_matches(date: Option<&str>, query: &str) -> bool {
+    let Some(date) = date.map(str::trim) else {
+        return false;
+    };
+    let prefix = |len: usize| date.get(..len).unwrap_or(date);
+    match query.split_once("..") {
+        Some((from, to)) => {
+            (from.is_empty() || prefix(from.len()) >= from)
+                && (to.is_empty() || prefix(to.len()) <= to)
+        }
+        None => date.starts_with(query),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Name,   // Natural order of the path, as scanned
+    Date,   // DT, oldest first
+    Event,  // EV, then date
+    Player, // Black player, then white
+}
+
+impl SortKey {
+    pub fn from_name(name: &str) -> Option<SortKey> {
+        match name.to_ascii_lowercase().as_str() {
+            "name" | "file" => Some(SortKey::Name),
+            "date" => Some(SortKey::Date),
+            "event" => Some(SortKey::Event),
+            "player" => Some(SortKey::Player),
+            _ => None,
+        }
+    }
+}
+
+// What happens when a game reaches its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    #[default]
+    All, // Move on to the next game, wrapping around after the last one
+    One, // Replay the current game
+    Off, // Move on to the next game and stop after the last one
+}
+
+impl RepeatMode {
+    pub fn from_name(name: &str) -> Option<RepeatMode> {
+        match name.to_ascii_lowercase().as_str() {
+            "all" => Some(RepeatMode::All),
+            "one" => Some(RepeatMode::One),
+            "off" | "none" => Some(RepeatMode::Off),
+            _ => None,
+        }
+    }
+
+    // All -> One -> Off -> All, the order of the L key
+    pub fn cycle(self) -> RepeatMode {
+        match self {
+            RepeatMode::All => RepeatMode::One,
+            RepeatMode::One => RepeatMode::Off,
+            RepeatMode::Off => RepeatMode::All,
+        }
+    }
+}
+
+// A shuffle seed that differs between runs
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+// How folders (and archives) are searched for games. Patterns without a '/'
+// match the file name anywhere in the tree; patterns with one match the path
+// relative to the scanned folder, e.g. "2023/round*/*.sgf" or "**/drafts/**"
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    pub max_depth: Option<usize>, // 0 = only the folder itself; None = unlimited
+    pub include: Vec<String>,     // Only files matching one of these (all when empty)
+    pub exclude: Vec<String>,     // Skip files and folders matching any of these
+    pub follow_symlinks: bool,    // Descend into symlinked folders (loops are skipped)
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            max_depth: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            follow_symlinks: true,
+        }
+    }
+}
+
+impl ScanOptions {
+    // Check the patterns up front so typos are reported instead of matching nothing
+    pub fn validate(&self) -> Result<(), String> {
+        for pattern in self.include.iter().chain(&self.exclude) {
+            glob::Pattern::new(pattern)
+                .map_err(|e| format!("Invalid pattern {}: {}", pattern, e))?;
+        }
+        Ok(())
+    }
+
+    fn is_included(&self, relative: &str) -> bool {
+        self.include.is_empty() || self.include.iter().any(|p| glob_matches(p, relative))
+    }
+
+    fn is_excluded(&self, relative: &str) -> bool {
+        self.exclude.iter().any(|p| glob_matches(p, relative))
+    }
+
+    // Excluded itself or inside an excluded folder
+    fn is_excluded_path(&self, relative: &str) -> bool {
+        relative
+            .match_indices('/')
+            .map(|(idx, _)| &relative[..idx])
+            .chain([relative])
+            .any(|prefix| self.is_excluded(prefix))
+    }
+
+    fn within_depth(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max| depth <= max)
+    }
+}
+
+fn glob_matches(pattern: &str, relative: &str) -> bool {
+    let Ok(glob) = glob::Pattern::new(pattern) else {
+        return false;
+    };
+    let options = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+    if pattern.contains('/') {
+        glob.matches_with(relative, options)
+    } else {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        glob.matches_with(name, options)
+    }
+}
+
+// Relative path with '/' separators on every platform
+fn relative_name(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let parts: Vec<String> = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy().to_string())
+        .collect();
+    parts.join("/")
+}
 
 pub struct PlaylistManager {
     files: Vec<PathBuf>,
     current_index: usize,
+    in_memory: HashMap<PathBuf, String>, // SGF text of entries with no file behind them (stdin)
+    repeat: RepeatMode,
+    shuffle_seed: Option<u64>,
+    unshuffled: Vec<PathBuf>, // Order to restore when shuffle is turned off
+    entries: HashMap<PathBuf, PlaylistEntry>, // Start/end move and note from a playlist file
+    folder: Option<(PathBuf, ScanOptions)>, // Scanned folder, for refresh
+    query: GameQuery,         // Last filter, applied to new files on refresh
+    sort_key: Option<SortKey>, // Last sort, applied to new files on refresh
+    archives: RefCell<ArchiveCache>, // Bundles read so far, so each is decompressed once
+}
+
+// Files that appeared in or disappeared from a watched folder
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlaylistChanges {
+    pub added: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl PlaylistChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
 
 impl PlaylistManager {
     pub fn new(path: Option<&str>) -> Result<Self, io::Error> {
+        Self::with_options(path, &ScanOptions::default())
+    }
+
+    pub fn with_options(path: Option<&str>, options: &ScanOptions) -> Result<Self, io::Error> {
+        let folder = match path {
+            Some(STDIN_ARG) => None,
+            Some(p) => Some(Path::new(p)).filter(|path| path.is_dir()),
+            None => Some(Path::new("./sgf")),
+        };
+        let mut archives = ArchiveCache::default();
         let files = match path {
+            Some(STDIN_ARG) => return Self::from_reader(io::stdin()),
             Some(p) => {
                 let path_obj = Path::new(p);
-                if path_obj.is_file() {
+                if path_obj.is_file() && playlist_file::is_playlist_file(path_obj) {
+                    return Self::from_playlist_file(path_obj);
+                } else if path_obj.is_file() && archive::is_archive(path_obj) {
+                    Self::scan_archive(path_obj, options, &mut archives)?
+                } else if path_obj.is_file() {
                     vec![PathBuf::from(p)]
                 } else if path_obj.is_dir() {
-                    Self::scan_folder(p)?
+                    Self::scan_folder(path_obj, options)?
                 } else {
                     return Err(io::Error::new(
                         io::ErrorKind::NotFound,
@@ -23,7 +340,7 @@ impl PlaylistManager {
                     ));
                 }
             }
-            None => Self::scan_folder("./sgf")?,
+            None => Self::scan_folder(Path::new("./sgf"), options)?,
         };
 
         if files.is_empty() {
@@ -33,31 +350,271 @@ impl PlaylistManager {
             ));
         }
 
-        Ok(PlaylistManager {
+        let mut playlist = Self::with_files(files);
+        playlist.archives = RefCell::new(archives);
+        playlist.folder = folder.map(|folder| (folder.to_path_buf(), options.clone()));
+        Ok(playlist)
+    }
+
+    fn with_files(files: Vec<PathBuf>) -> Self {
+        PlaylistManager {
             files,
             current_index: 0,
-        })
+            in_memory: HashMap::new(),
+            repeat: RepeatMode::default(),
+            shuffle_seed: None,
+            unshuffled: Vec::new(),
+            entries: HashMap::new(),
+            folder: None,
+            query: GameQuery::default(),
+            sort_key: None,
+            archives: RefCell::default(),
+        }
+    }
+
+    // Read a single game or an SGF collection, e.g. piped from stdin
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, io::Error> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        let games = parser::split_collection(&input);
+        if games.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "No SGF games found on stdin",
+            ));
+        }
+
+        let mut files = Vec::new();
+        let mut in_memory = HashMap::new();
+        for (idx, game) in games.iter().enumerate() {
+            let name = if games.len() == 1 {
+                PathBuf::from("<stdin>")
+            } else {
+                PathBuf::from(format!("<stdin>#{}", idx + 1))
+            };
+            files.push(name.clone());
+            in_memory.insert(name, game.to_string());
+        }
+
+        let mut playlist = Self::with_files(files);
+        playlist.in_memory = in_memory;
+        Ok(playlist)
     }
 
-    fn scan_folder(path: &str) -> Result<Vec<PathBuf>, io::Error> {
-        let mut files: Vec<PathBuf> = fs::read_dir(path)?
+    // Load a saved playlist; games play in the listed order
+    pub fn from_playlist_file(path: &Path) -> Result<Self, io::Error> {
+        let listed = playlist_file::read_playlist(path)?;
+        if listed.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No games listed in {}", path.display()),
+            ));
+        }
+
+        let files = listed.iter().map(|entry| entry.path.clone()).collect();
+        let mut entries = HashMap::new();
+        for entry in listed {
+            // A game listed twice keeps the options of its first listing
+            entries.entry(entry.path.clone()).or_insert(entry);
+        }
+
+        let mut playlist = Self::with_files(files);
+        playlist.entries = entries;
+        Ok(playlist)
+    }
+
+    // Write the games in their current order, with any playlist file options
+    pub fn save(&self, path: &Path) -> Result<(), io::Error> {
+        if let Some(stdin_entry) = self
+            .files
+            .iter()
+            .find(|file| self.in_memory.contains_key(*file))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} was read from stdin and can't be saved in a playlist",
+                    stdin_entry.display()
+                ),
+            ));
+        }
+        let entries: Vec<PlaylistEntry> = (0..self.files.len())
+            .filter_map(|index| self.entry(index))
+            .collect();
+        playlist_file::write_playlist(path, &entries)
+    }
+
+    // The game at `index` with its playlist file options (none for scanned games)
+    pub fn entry(&self, index: usize) -> Option<PlaylistEntry> {
+        let path = self.files.get(index)?;
+        Some(
+            self.entries
+                .get(path)
+                .cloned()
+                .unwrap_or_else(|| PlaylistEntry::new(path)),
+        )
+    }
+
+    // Whether the playlist came from a folder that refresh can rescan
+    pub fn is_watchable(&self) -> bool {
+        self.folder.is_some()
+    }
+
+    // Rescan the folder the playlist came from. New files (that pass the last
+    // filter) are inserted next to their neighbour in the folder's natural
+    // order, or where the last sort puts them; deleted files are dropped. The
+    // current game stays in the playlist even if its file is gone, until
+    // playback moves on from it
+    pub fn refresh(&mut self) -> Result<PlaylistChanges, io::Error> {
+        let Some((root, options)) = &self.folder else {
+            return Ok(PlaylistChanges::default());
+        };
+        let scanned = Self::scan_folder(root, options)?;
+        let current = self.files[self.current_index].clone();
+
+        let on_disk: HashSet<&PathBuf> = scanned.iter().collect();
+        let removed: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|path| !on_disk.contains(path) && **path != current)
+            .cloned()
+            .collect();
+        let known: HashSet<&PathBuf> = self.files.iter().collect();
+        let added: Vec<PathBuf> = scanned
+            .iter()
+            .filter(|path| !known.contains(path))
+            .filter(|path| self.query.is_empty() || self.query.matches(&self.summary(path)))
+            .cloned()
+            .collect();
+
+        let changes = PlaylistChanges { added, removed };
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+        self.files = merge_scan(&self.files, &scanned, &changes);
+        if self.shuffle_seed.is_some() {
+            self.unshuffled = merge_scan(&self.unshuffled, &scanned, &changes);
+        }
+        if let Some(key) = self.sort_key {
+            if self.shuffle_seed.is_some() {
+                self.unshuffled = self.sorted(&self.unshuffled, key);
+            } else {
+                self.files = self.sorted(&self.files, key);
+            }
+        }
+        self.current_index = self.position_of(&current);
+        Ok(changes)
+    }
+
+    // Walk the folder tree, then sort by the natural order of the relative
+    // path so "round2/game10" comes after "round2/game9" and before "round10"
+    fn scan_folder(root: &Path, options: &ScanOptions) -> Result<Vec<PathBuf>, io::Error> {
+        let mut files = Vec::new();
+        let mut visited = Vec::new();
+        if let Ok(canonical) = fs::canonicalize(root) {
+            visited.push(canonical);
+        }
+        Self::walk_folder(root, root, 0, options, &mut visited, &mut files)?;
+
+        files.sort_by(|a, b| Self::natural_path_compare(a, b));
+        Ok(files)
+    }
+
+    fn walk_folder(
+        root: &Path,
+        dir: &Path,
+        depth: usize,
+        options: &ScanOptions,
+        visited: &mut Vec<PathBuf>,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), io::Error> {
+        let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
             .filter_map(|entry| entry.ok())
             .map(|entry| entry.path())
-            .filter(|path| {
-                path.extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|ext| ext.eq_ignore_ascii_case("sgf"))
-                    .unwrap_or(false)
-            })
             .collect();
+        entries.sort();
 
-        files.sort_by(|a, b| Self::natural_sort_compare(a, b));
+        for path in entries {
+            let relative = relative_name(&path, root);
+            if options.is_excluded(&relative) {
+                continue;
+            }
+
+            let is_symlink = fs::symlink_metadata(&path)
+                .map(|meta| meta.file_type().is_symlink())
+                .unwrap_or(false);
+            if path.is_dir() {
+                if !options.within_depth(depth + 1) || (is_symlink && !options.follow_symlinks) {
+                    continue;
+                }
+                // Symlinks can point back up the tree; never visit a folder twice
+                let Ok(canonical) = fs::canonicalize(&path) else {
+                    continue;
+                };
+                if visited.contains(&canonical) {
+                    continue;
+                }
+                visited.push(canonical);
+                // Unreadable subfolders are skipped rather than failing the whole scan
+                let _ = Self::walk_folder(root, &path, depth + 1, options, visited, files);
+            } else if path.is_file()
+                && import::is_game_file(&path)
+                && options.is_included(&relative)
+            {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    // Archive entries are filtered and sorted by their full name inside the
+    // archive, the same way folder contents are
+    fn scan_archive(
+        path: &Path,
+        options: &ScanOptions,
+        archives: &mut ArchiveCache,
+    ) -> Result<Vec<PathBuf>, io::Error> {
+        let mut files: Vec<PathBuf> = archives
+            .list_entries(path)?
+            .into_iter()
+            .filter(|entry| {
+                let relative = relative_name(entry, path);
+                let depth = relative.matches('/').count();
+                options.within_depth(depth)
+                    && options.is_included(&relative)
+                    && !options.is_excluded_path(&relative)
+            })
+            .collect();
+        files.sort_by(|a, b| Self::natural_path_compare(a, b));
         Ok(files)
     }
 
+    // Paths compared folder by folder, so "round2/game10" comes after
+    // "round2/game9" and before "round10/game1"
+    fn natural_path_compare(a: &Path, b: &Path) -> std::cmp::Ordering {
+        let mut a_parts = a.components();
+        let mut b_parts = b.components();
+        loop {
+            match (a_parts.next(), b_parts.next()) {
+                (Some(a_part), Some(b_part)) => {
+                    let order = Self::natural_sort_compare(
+                        Path::new(a_part.as_os_str()),
+                        Path::new(b_part.as_os_str()),
+                    );
+                    if order.is_ne() {
+                        return order;
+                    }
+                }
+                (a_part, b_part) => return a_part.is_some().cmp(&b_part.is_some()),
+            }
+        }
+    }
+
     fn natural_sort_compare(a: &Path, b: &Path) -> std::cmp::Ordering {
-        let a_name = a.file_stem().unwrap().to_string_lossy();
-        let b_name = b.file_stem().unwrap().to_string_lossy();
+        // Root and prefix components have no stem; compare them whole
+        let a_name = a.file_stem().unwrap_or(a.as_os_str()).to_string_lossy();
+        let b_name = b.file_stem().unwrap_or(b.as_os_str()).to_string_lossy();
 
         let a_tokens = Self::tokenize(&a_name);
         let b_tokens = Self::tokenize(&b_name);
@@ -108,6 +665,7 @@ impl PlaylistManager {
         self.current_index + 1 < self.files.len()
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> bool {
         if self.has_next() {
             self.current_index += 1;
@@ -125,6 +683,248 @@ impl PlaylistManager {
         }
     }
 
+    pub fn has_previous(&self) -> bool {
+        self.current_index > 0
+    }
+
+    pub fn previous(&mut self) -> bool {
+        if self.has_previous() {
+            self.current_index -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // The game after the current one; with RepeatMode::All the last game wraps
+    // around to the first
+    pub fn next_index(&self) -> Option<usize> {
+        if self.has_next() {
+            Some(self.current_index + 1)
+        } else if self.repeat == RepeatMode::All && self.files.len() > 1 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    // The game before the current one; with RepeatMode::All the first game
+    // wraps around to the last
+    pub fn previous_index(&self) -> Option<usize> {
+        if self.has_previous() {
+            Some(self.current_index - 1)
+        } else if self.repeat == RepeatMode::All && self.files.len() > 1 {
+            Some(self.files.len() - 1)
+        } else {
+            None
+        }
+    }
+
+    // The game to play once the current one has ended, or None to stay on it
+    // (replaying it if it loops)
+    pub fn index_after_end(&self) -> Option<usize> {
+        match self.repeat {
+            RepeatMode::One => None,
+            RepeatMode::All | RepeatMode::Off => self.next_index(),
+        }
+    }
+
+    pub fn repeat(&self) -> RepeatMode {
+        self.repeat
+    }
+
+    pub fn set_repeat(&mut self, mode: RepeatMode) {
+        self.repeat = mode;
+    }
+
+    // Put the games in a random order that only depends on `seed`. The current
+    // game stays current; shuffling again reshuffles the original order
+    pub fn shuffle(&mut self, seed: u64) {
+        if self.shuffle_seed.is_none() {
+            self.unshuffled = self.files.clone();
+        }
+        let current = self.files[self.current_index].clone();
+
+        let mut files = self.unshuffled.clone();
+        let mut state = seed;
+        for i in (1..files.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            files.swap(i, j);
+        }
+
+        self.files = files;
+        self.shuffle_seed = Some(seed);
+        self.current_index = self.position_of(&current);
+    }
+
+    // Restore the order from before shuffle, keeping the current game
+    pub fn unshuffle(&mut self) {
+        if self.shuffle_seed.take().is_none() {
+            return;
+        }
+        let current = self.files[self.current_index].clone();
+        self.files = std::mem::take(&mut self.unshuffled);
+        self.current_index = self.position_of(&current);
+    }
+
+    pub fn shuffle_seed(&self) -> Option<u64> {
+        self.shuffle_seed
+    }
+
+    // Index of the game at `path` (compared as absolute paths), appending it to
+    // the playlist if it isn't there, e.g. to open a bookmark from elsewhere
+    pub fn find_or_add(&mut self, path: &Path) -> usize {
+        let absolute =
+            |path: &Path| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let wanted = absolute(path);
+        if let Some(index) = self.files.iter().position(|file| absolute(file) == wanted) {
+            return index;
+        }
+        self.files.push(path.to_path_buf());
+        if self.shuffle_seed.is_some() {
+            self.unshuffled.push(path.to_path_buf());
+        }
+        self.files.len() - 1
+    }
+
+    // Games read from stdin have no file behind them
+    pub fn is_in_memory(&self, path: &Path) -> bool {
+        self.in_memory.contains_key(path)
+    }
+
+    fn position_of(&self, path: &Path) -> usize {
+        self.files.iter().position(|file| file == path).unwrap_or(0)
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current_index
+    }
+
+    pub fn jump_to(&mut self, index: usize) -> bool {
+        if index < self.files.len() {
+            self.current_index = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn summaries(&self) -> Vec<GameSummary> {
+        self.files.iter().map(|path| self.summary(path)).collect()
+    }
+
+    fn summary(&self, path: &Path) -> GameSummary {
+        match self.in_memory.get(path) {
+            Some(content) => {
+                let properties = parser::parse_sgf(content)
+                    .map(|tree| tree.properties)
+                    .unwrap_or_default();
+                GameSummary::from_properties(path, &properties)
+            }
+            None => GameSummary::load_cached(path, &mut self.archives.borrow_mut()),
+        }
+    }
+
+    // Keep only the games whose metadata matches; returns how many are left.
+    // Playback restarts from the first remaining game
+    pub fn filter(&mut self, query: &GameQuery) -> usize {
+        self.query = query.clone();
+        let files = std::mem::take(&mut self.files);
+        self.files = files
+            .into_iter()
+            .filter(|path| query.matches(&self.summary(path)))
+            .collect();
+        let kept: HashSet<&PathBuf> = self.files.iter().collect();
+        self.unshuffled.retain(|path| kept.contains(path));
+        self.current_index = 0;
+        self.files.len()
+    }
+
+    // Reorder by metadata; games missing the field go last, ties keep their order.
+    // Sorting turns shuffle off
+    pub fn sort(&mut self, key: SortKey) {
+        self.shuffle_seed = None;
+        self.unshuffled.clear();
+        self.sort_key = Some(key);
+        self.files = self.sorted(&self.files, key);
+        self.current_index = 0;
+    }
+
+    fn sorted(&self, files: &[PathBuf], key: SortKey) -> Vec<PathBuf> {
+        let mut files = files.to_vec();
+        if key == SortKey::Name {
+            files.sort_by(|a, b| Self::natural_path_compare(a, b));
+            return files;
+        }
+        let mut entries: Vec<_> = files
+            .into_iter()
+            .map(|path| (Self::sort_fields(&self.summary(&path), key), path))
+            .collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        entries.into_iter().map(|(_, path)| path).collect()
+    }
+
+    // (missing, lowercased value) per field, so missing values compare greater
+    fn sort_fields(summary: &GameSummary, key: SortKey) -> Vec<(bool, String)> {
+        let fields = match key {
+            SortKey::Name => vec![],
+            SortKey::Date => vec![&summary.date],
+            SortKey::Event => vec![&summary.event, &summary.date],
+            SortKey::Player => vec![&summary.black, &summary.white],
+        };
+        fields
+            .into_iter()
+            .map(|field| match field {
+                Some(value) => (false, value.to_lowercase()),
+                None => (true, String::new()),
+            })
+            .collect()
+    }
+
+    // Load the game at `index`, whether it lives on disk, in an archive or in
+    // memory. Games from a playlist file end at their end move and open at
+    // their start move
+    pub fn load(&self, index: usize) -> Result<GameState, io::Error> {
+        let path = self.files.get(index).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No playlist entry {}", index),
+            )
+        })?;
+        match self.in_memory.get(path) {
+            Some(content) => {
+                let tree = import::GameFormat::Sgf.parse(content).map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse {}: {:?}", path.display(), e),
+                    )
+                })?;
+                Ok(GameState::from_tree(tree))
+            }
+            None => {
+                let tree = import::read_game_tree_cached(path, &mut self.archives.borrow_mut())?;
+                let mut game = GameState::from_tree(tree);
+                if let Some(entry) = self.entries.get(path) {
+                    if let Some(end) = entry.end_move {
+                        game.moves.truncate(end);
+                    }
+                    if let Some(start) = entry.start_move {
+                        game.set_start_move(start);
+                    }
+                }
+                Ok(game)
+            }
+        }
+    }
+
+    pub fn load_current(&self) -> Result<GameState, io::Error> {
+        self.load(self.current_index)
+    }
+
     pub fn reset(&mut self) {
         self.current_index = 0;
     }
@@ -138,6 +938,36 @@ impl PlaylistManager {
     }
 }
 
+// `files` without the removed paths and with the added ones, each placed right
+// after the nearest path that precedes it in `scanned` (or first if none does)
+fn merge_scan(files: &[PathBuf], scanned: &[PathBuf], changes: &PlaylistChanges) -> Vec<PathBuf> {
+    let mut merged: Vec<PathBuf> = files
+        .iter()
+        .filter(|path| !changes.removed.contains(path))
+        .cloned()
+        .collect();
+    for added in &changes.added {
+        let scan_position = scanned.iter().position(|path| path == added).unwrap_or(0);
+        let insert_at = scanned[..scan_position]
+            .iter()
+            .rev()
+            .find_map(|neighbour| merged.iter().position(|path| path == neighbour))
+            .map_or(0, |index| index + 1);
+        merged.insert(insert_at, added.clone());
+    }
+    merged
+}
+
+// SplitMix64: a tiny, well-mixed generator so shuffles are reproducible
+// without pulling in a random number crate
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum Token {
     Text(String),
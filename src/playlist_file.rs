@@ -0,0 +1,174 @@
+// Hand-curated playlists saved as plain text. Each game path starts a line;
+// indented "key = value" lines below it set options for that entry:
+//
+//   # Lee Sedol study set
+//   2016/AlphaGo_LeeSedol_game4.sgf
+//       start = 70
+//       end = 90
+//       note = Move 78, the wedge
+//   collections/kisei.zip/1999/game2.sgf
+//
+// Relative paths are resolved against the playlist file's folder, so a
+// playlist can be moved together with its games.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+pub const PLAYLIST_EXTENSION: &str = "sgfplaylist";
+
+const HEADER: &str = "# smartgameviewer playlist";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlaylistEntry {
+    pub path: PathBuf,
+    pub start_move: Option<usize>, // Open the game at this move
+    pub end_move: Option<usize>,   // Treat this move as the end of the game
+    pub note: Option<String>,
+}
+
+impl PlaylistEntry {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        PlaylistEntry {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn is_playlist_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(PLAYLIST_EXTENSION))
+}
+
+pub fn read_playlist(path: &Path) -> Result<Vec<PlaylistEntry>, io::Error> {
+    let text = fs::read_to_string(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    parse_playlist(&text, base).map_err(|message| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}:{}", path.display(), message),
+        )
+    })
+}
+
+pub fn write_playlist(path: &Path, entries: &[PlaylistEntry]) -> Result<(), io::Error> {
+    let base = path.parent().unwrap_or(Path::new(""));
+    fs::write(path, format_playlist(entries, base)?)
+}
+
+// Errors are "<line>: <message>"
+pub fn parse_playlist(text: &str, base: &Path) -> Result<Vec<PlaylistEntry>, String> {
+    let mut entries: Vec<PlaylistEntry> = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let number = number + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with([' ', '\t']) {
+            let path = Path::new(line.trim_end());
+            entries.push(PlaylistEntry::new(if path.is_relative() {
+                base.join(path)
+            } else {
+                path.to_path_buf()
+            }));
+            continue;
+        }
+
+        let entry = entries
+            .last_mut()
+            .ok_or_else(|| format!("{}: option before the first game", number))?;
+        let (key, value) = trimmed
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| format!("{}: expected \"key = value\"", number))?;
+        let move_number = || {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("{}: invalid move number: {}", number, value))
+        };
+        match key {
+            "start" => entry.start_move = Some(move_number()?),
+            "end" => entry.end_move = Some(move_number()?),
+            "note" => entry.note = Some(value.to_string()),
+            _ => return Err(format!("{}: unknown option: {}", number, key)),
+        }
+        if let (Some(start), Some(end)) = (entry.start_move, entry.end_move) {
+            if start > end {
+                return Err(format!(
+                    "{}: start move {} is after end move {}",
+                    number, start, end
+                ));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+// Paths inside `base`'s tree (or near it) are written relative to it
+pub fn format_playlist(entries: &[PlaylistEntry], base: &Path) -> Result<String, io::Error> {
+    let base = std::path::absolute(base)?;
+    let mut text = format!("{}\n", HEADER);
+    for entry in entries {
+        let path = std::path::absolute(&entry.path)?;
+        let path = relative_path(&path, &base).unwrap_or(path);
+        text.push_str(&path.to_string_lossy());
+        text.push('\n');
+        if let Some(start) = entry.start_move {
+            text.push_str(&format!("    start = {}\n", start));
+        }
+        if let Some(end) = entry.end_move {
+            text.push_str(&format!("    end = {}\n", end));
+        }
+        if let Some(note) = &entry.note {
+            // Notes are single-line
+            let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+            text.push_str(&format!("    note = {}\n", note));
+        }
+    }
+    Ok(text)
+}
+
+// `path` relative to `base`, both absolute; None when they only share the
+// root (or not even that, e.g. different drives on Windows)
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    let path: Vec<Component> = normalized(path);
+    let base: Vec<Component> = normalized(base);
+    let root = base
+        .iter()
+        .take_while(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+        .count();
+
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    if common <= root {
+        return None;
+    }
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &path[common..] {
+        relative.push(component);
+    }
+    Some(relative)
+}
+
+// Components with "." and "dir/.." pairs removed
+fn normalized(path: &Path) -> Vec<Component<'_>> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                components.pop();
+            }
+            _ => components.push(component),
+        }
+    }
+    components
+}
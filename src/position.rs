@@ -0,0 +1,162 @@
+// Whole-board position search across a game library.
+//
+// Every board carries a Zobrist hash: each stone has a fixed pseudo-random key
+// and a position's hash is the XOR of the keys of its stones, so placing or
+// removing a stone updates it in one step. The board keeps one hash for each
+// of the 8 symmetries with and without colours swapped; the smallest of the
+// 16 identifies the position up to symmetry and colour swap.
+
+use crate::game::GameState;
+use crate::parser::Color;
+use crate::playlist::PlaylistManager;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// Rotations and reflections of the board
+pub const SYMMETRIES: u8 = 8;
+
+// Where a point lands under one of the 8 symmetries. Symmetry 0 is the
+// identity; bit 0 mirrors columns, bit 1 mirrors rows, bit 2 swaps rows and columns
+pub fn transform(symmetry: u8, size: u8, (row, col): (u8, u8)) -> (u8, u8) {
+    let last = size - 1;
+    let (row, col) = if symmetry & 4 != 0 {
+        (col, row)
+    } else {
+        (row, col)
+    };
+    let row = if symmetry & 2 != 0 { last - row } else { row };
+    let col = if symmetry & 1 != 0 { last - col } else { col };
+    (row, col)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionHash {
+    variants: Box<[u64; 2 * SYMMETRIES as usize]>, // [symmetry * 2 + colours swapped]
+}
+
+impl PositionHash {
+    // The empty board; boards of different sizes never share a hash
+    pub fn empty(size: u8) -> Self {
+        PositionHash {
+            variants: Box::new([mix(size as u64); 2 * SYMMETRIES as usize]),
+        }
+    }
+
+    // Add a stone, or remove it if it is there
+    pub fn toggle(&mut self, size: u8, point: (u8, u8), color: &Color) {
+        for symmetry in 0..SYMMETRIES {
+            let (row, col) = transform(symmetry, size, point);
+            for swapped in [false, true] {
+                let black = (*color == Color::Black) != swapped;
+                let index = symmetry as usize * 2 + swapped as usize;
+                self.variants[index] ^= stone_key(size, row, col, black);
+            }
+        }
+    }
+
+    // This exact position, as it stands on the board
+    pub fn exact(&self) -> u64 {
+        self.variants[0]
+    }
+
+    // The same for every rotation, reflection and colour swap of the position
+    pub fn canonical(&self) -> u64 {
+        self.variants.iter().copied().min().unwrap_or_default()
+    }
+}
+
+fn stone_key(size: u8, row: u8, col: u8, black: bool) -> u64 {
+    mix(((size as u64) << 24 | (row as u64) << 16 | (col as u64) << 8 | black as u64) + 1)
+}
+
+// SplitMix64 finalizer, spreading nearby inputs over the whole range
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// A game and move where a searched position occurred
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionHit {
+    pub path: PathBuf,
+    pub move_number: usize,
+    pub exact: bool, // Same orientation and colours as the searched position
+}
+
+struct Occurrence {
+    game: usize,
+    move_number: usize,
+    exact: u64,
+}
+
+// Canonical hashes of every position reached in a set of games
+#[derive(Default)]
+pub struct PositionIndex {
+    games: Vec<PathBuf>,
+    positions: HashMap<u64, Vec<Occurrence>>,
+    unreadable: Vec<PathBuf>,
+}
+
+impl PositionIndex {
+    // Index every game in the playlist; games that fail to load are listed
+    // in `unreadable`
+    pub fn build(playlist: &PlaylistManager) -> Self {
+        let mut index = PositionIndex::default();
+        for (number, path) in playlist.files().iter().enumerate() {
+            match playlist.load(number) {
+                Ok(game) => index.add_game(path, game),
+                Err(_) => index.unreadable.push(path.clone()),
+            }
+        }
+        index
+    }
+
+    // Replay a game from its first move (not a playlist entry's start move),
+    // recording the first move at which each position occurs (the empty board
+    // isn't recorded)
+    pub fn add_game(&mut self, path: &Path, mut game: GameState) {
+        let number = self.games.len();
+        self.games.push(path.to_path_buf());
+
+        let mut seen = HashSet::new();
+        game.jump_to_move(0);
+        while game.current_move < game.moves.len() {
+            game.next();
+            let hash = game.position_hash();
+            if seen.insert(hash.canonical()) {
+                self.positions
+                    .entry(hash.canonical())
+                    .or_default()
+                    .push(Occurrence {
+                        game: number,
+                        move_number: game.current_move,
+                        exact: hash.exact(),
+                    });
+            }
+        }
+    }
+
+    pub fn games(&self) -> &[PathBuf] {
+        &self.games
+    }
+
+    pub fn unreadable(&self) -> &[PathBuf] {
+        &self.unreadable
+    }
+
+    // Every game that reached the position, in index order
+    pub fn find(&self, position: &PositionHash) -> Vec<PositionHit> {
+        self.positions
+            .get(&position.canonical())
+            .into_iter()
+            .flatten()
+            .map(|occurrence| PositionHit {
+                path: self.games[occurrence.game].clone(),
+                move_number: occurrence.move_number,
+                exact: occurrence.exact == position.exact(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+// Where the viewer left off, kept between runs in a small tab-separated file
+// under the user's data directory:
+//
+//   move      <game path>      <move number>   last move viewed in a game
+//   playlist  <playlist path>  <game path>     last game played from a playlist
+//
+// Paths are stored absolute so the viewer can be started from any folder.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# smartgameviewer state";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ViewerState {
+    moves: HashMap<PathBuf, usize>,
+    playlists: HashMap<PathBuf, PathBuf>,
+}
+
+impl ViewerState {
+    // <data dir>/smartgameviewer/state, e.g. ~/.local/share on Linux
+    pub fn default_path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("smartgameviewer").join("state"))
+    }
+
+    // A missing or unreadable state file is an empty state; lines that don't
+    // parse are skipped
+    pub fn load(path: &Path) -> ViewerState {
+        let mut state = ViewerState::default();
+        let Ok(text) = fs::read_to_string(path) else {
+            return state;
+        };
+        for line in text.lines() {
+            match line.split('\t').collect::<Vec<_>>().as_slice() {
+                ["move", game, number] => {
+                    if let Ok(number) = number.parse() {
+                        state.moves.insert(PathBuf::from(game), number);
+                    }
+                }
+                ["playlist", playlist, game] => {
+                    state
+                        .playlists
+                        .insert(PathBuf::from(playlist), PathBuf::from(game));
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), io::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut moves: Vec<_> = self.moves.iter().collect();
+        moves.sort();
+        let mut playlists: Vec<_> = self.playlists.iter().collect();
+        playlists.sort();
+
+        let mut text = format!("{}\n", HEADER);
+        for (game, number) in moves {
+            text.push_str(&format!("move\t{}\t{}\n", game.display(), number));
+        }
+        for (playlist, game) in playlists {
+            text.push_str(&format!(
+                "playlist\t{}\t{}\n",
+                playlist.display(),
+                game.display()
+            ));
+        }
+        fs::write(path, text)
+    }
+
+    pub fn last_move(&self, game: &Path) -> Option<usize> {
+        self.moves.get(&key(game)).copied()
+    }
+
+    // Move 0 needs no resuming, so it clears the entry
+    pub fn set_last_move(&mut self, game: &Path, move_number: usize) {
+        if move_number == 0 {
+            self.moves.remove(&key(game));
+        } else {
+            self.moves.insert(key(game), move_number);
+        }
+    }
+
+    pub fn last_game(&self, playlist: &Path) -> Option<&Path> {
+        self.playlists.get(&key(playlist)).map(PathBuf::as_path)
+    }
+
+    pub fn set_last_game(&mut self, playlist: &Path, game: &Path) {
+        self.playlists.insert(key(playlist), key(game));
+    }
+
+    // Index in `files` of the game last played from `playlist`, and the move
+    // it was left at
+    pub fn resume_point(&self, playlist: &Path, files: &[PathBuf]) -> Option<(usize, usize)> {
+        let game = self.last_game(playlist)?;
+        let index = files.iter().position(|file| key(file) == game)?;
+        Some((index, self.last_move(game).unwrap_or(0)))
+    }
+}
+
+fn key(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
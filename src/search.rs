@@ -0,0 +1,185 @@
+// Text search within the current game. The walk visits every node of the main
+// line: node 0 is the root (its C, N and LB are game properties) and node n is
+// move n. Comments (C), node names (N) and label texts (LB) are matched
+// case-insensitively.
+
+use crate::game::GameState;
+use crate::parser;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchField {
+    Comment,
+    Name,
+    Label,
+}
+
+impl SearchField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchField::Comment => "Comment",
+            SearchField::Name => "Name",
+            SearchField::Label => "Label",
+        }
+    }
+}
+
+// A text containing the query, with the byte range of each occurrence
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMatch {
+    pub field: SearchField,
+    pub text: String,
+    pub ranges: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMatch {
+    pub move_number: usize,
+    pub fields: Vec<FieldMatch>,
+}
+
+pub struct GameSearch {
+    query: String,
+    matches: Vec<NodeMatch>, // In move order
+}
+
+impl GameSearch {
+    pub fn new(game: &GameState, query: &str) -> Self {
+        let query = query.trim();
+        let mut matches: Vec<NodeMatch> = Vec::new();
+
+        for (move_number, field, text) in node_texts(game) {
+            let ranges = find_matches(&text, query);
+            if ranges.is_empty() {
+                continue;
+            }
+            let found = FieldMatch {
+                field,
+                text,
+                ranges,
+            };
+            match matches.last_mut() {
+                Some(last) if last.move_number == move_number => last.fields.push(found),
+                _ => matches.push(NodeMatch {
+                    move_number,
+                    fields: vec![found],
+                }),
+            }
+        }
+
+        GameSearch {
+            query: query.to_string(),
+            matches,
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn matches(&self) -> &[NodeMatch] {
+        &self.matches
+    }
+
+    // Index in `matches()` of the match at a move
+    pub fn position(&self, move_number: usize) -> Option<usize> {
+        self.matches
+            .iter()
+            .position(|found| found.move_number == move_number)
+    }
+
+    pub fn at(&self, move_number: usize) -> Option<&NodeMatch> {
+        self.position(move_number).map(|index| &self.matches[index])
+    }
+
+    // The next matching move after `move_number`, wrapping around to the first
+    pub fn next_after(&self, move_number: usize) -> Option<usize> {
+        let mut numbers = self.matches.iter().map(|found| found.move_number);
+        numbers
+            .clone()
+            .find(|&number| number > move_number)
+            .or_else(|| numbers.next())
+    }
+
+    // The previous matching move before `move_number`, wrapping around to the last
+    pub fn previous_before(&self, move_number: usize) -> Option<usize> {
+        let mut numbers = self.matches.iter().rev().map(|found| found.move_number);
+        numbers
+            .clone()
+            .find(|&number| number < move_number)
+            .or_else(|| numbers.next())
+    }
+}
+
+// Every searchable text in the game, in node order
+pub fn node_texts(game: &GameState) -> Vec<(usize, SearchField, String)> {
+    let mut texts = Vec::new();
+
+    let root = |key: &str| game.properties.get(key).into_iter().flatten();
+    texts.extend(
+        root("C")
+            .take(1)
+            .map(|text| (0, SearchField::Comment, text.clone())),
+    );
+    texts.extend(
+        root("N")
+            .take(1)
+            .map(|text| (0, SearchField::Name, text.clone())),
+    );
+    texts.extend(
+        root("LB")
+            .filter_map(|value| parser::parse_label(value))
+            .map(|label| (0, SearchField::Label, label.text)),
+    );
+
+    for (index, mv) in game.moves.iter().enumerate() {
+        let move_number = index + 1;
+        if let Some(comment) = &mv.comment {
+            texts.push((move_number, SearchField::Comment, comment.clone()));
+        }
+        if let Some(name) = &mv.name {
+            texts.push((move_number, SearchField::Name, name.clone()));
+        }
+        for label in &mv.labels {
+            texts.push((move_number, SearchField::Label, label.text.clone()));
+        }
+    }
+
+    texts
+}
+
+// Byte ranges of the non-overlapping, case-insensitive occurrences of `query`
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (start, _) in text.char_indices() {
+        if ranges.last().is_some_and(|last| start < last.end) {
+            continue;
+        }
+        if let Some(len) = match_len(&text[start..], &query) {
+            ranges.push(start..start + len);
+        }
+    }
+    ranges
+}
+
+// Length in bytes of the prefix of `text` that equals `query` ignoring case
+fn match_len(text: &str, query: &[char]) -> Option<usize> {
+    let mut rest = query;
+    for (index, ch) in text.char_indices() {
+        for lower in ch.to_lowercase() {
+            match rest.split_first() {
+                Some((&first, tail)) if first == lower => rest = tail,
+                _ => return None,
+            }
+        }
+        if rest.is_empty() {
+            return Some(index + ch.len_utf8());
+        }
+    }
+    None
+}
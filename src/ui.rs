@@ -1,35 +1,463 @@
-use crate::board_view::BoardView;
+use crate::board_view::{BoardView, Intersection, BLACK_STONE, WHITE_STONE};
+use crate::bookmarks::Bookmark;
 use crate::game::{Board, GameState};
 use crate::parser::Color;
+use crate::pattern::{point_name, Corner, Pattern, PatternSearch, Region, CORNER_EXTENT};
+use crate::playlist::{GameSummary, PlaylistManager, RepeatMode};
+use crate::search::{FieldMatch, GameSearch};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color as RatatuiColor, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn render_game(frame: &mut Frame, game: &GameState, auto_play: bool, playback_speed: u64) {
-    // Calculate exact board height (19 lines for 19x19 board)
-    let board_height = game.board.size as u16;
+// Status bar elements that can be clicked with the mouse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusControl {
+    PlayPause,
+    Loop,
+    Speed,
+}
+
+// How the board is drawn, chosen from the space available
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BoardStyle {
+    // 2-column intersections joined by connectors, with padding and bordered bars
+    Regular,
+    // 1-column intersections, no padding, single-line header and status
+    Compact,
+}
+
+struct GameLayout {
+    header: Rect,
+    board: Rect,
+    spare: Rect, // Between the board and the status bar
+    status: Rect,
+    style: BoardStyle,
+}
+
+// Modal panels drawn on top of the game
+pub enum Overlay {
+    Help,
+    Info,
+    Browser(FileBrowser),
+    Resume(ResumeOffer),
+    BookmarkLabel(LabelPrompt),
+    Bookmarks(BookmarkList),
+    Search(SearchPrompt),
+    Pattern(Box<PatternPanel>), // Boxed: it holds a copy of the board
+}
+
+// Query being typed after `/`
+pub struct SearchPrompt {
+    pub text: String,
+}
+
+// Label typed for a new bookmark; the position is taken when the prompt opens
+pub struct LabelPrompt {
+    pub path: std::path::PathBuf,
+    pub move_number: usize,
+    pub text: String,
+}
+
+// State of the bookmark list overlay
+pub struct BookmarkList {
+    bookmarks: Vec<Bookmark>,
+    selected: usize,
+    error: Option<String>,
+}
+
+impl BookmarkList {
+    pub fn new(bookmarks: Vec<Bookmark>) -> Self {
+        BookmarkList {
+            bookmarks,
+            selected: 0,
+            error: None,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Bookmark> {
+        self.bookmarks.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.bookmarks.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    // Replace the entries after a change, keeping the selection in range
+    pub fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+        self.bookmarks = bookmarks;
+        self.selected = self.selected.min(self.bookmarks.len().saturating_sub(1));
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+}
+
+// Where the last session stopped, offered at launch
+pub struct ResumeOffer {
+    pub index: usize, // Playlist entry
+    pub move_number: usize,
+    pub name: String,
+    pub shown_at: std::time::Instant, // Unanswered offers expire, for unattended displays
+}
+
+// State of the pattern search overlay: a corner of the board as it was when
+// the overlay opened and, once searched, what followed it in the playlist
+pub struct PatternPanel {
+    board: Board,
+    corner: Corner,
+    extent: u8,
+    search: Option<PatternSearch>,
+    error: Option<String>,
+}
+
+impl PatternPanel {
+    // Starts on the corner of the last move
+    pub fn new(game: &GameState) -> Self {
+        let size = game.board.size;
+        let last_move = game
+            .current_move
+            .checked_sub(1)
+            .and_then(|index| game.moves[index].position);
+        let corner = match last_move {
+            Some((row, col)) => match (row * 2 < size, col * 2 < size) {
+                (true, true) => Corner::TopLeft,
+                (true, false) => Corner::TopRight,
+                (false, false) => Corner::BottomRight,
+                (false, true) => Corner::BottomLeft,
+            },
+            None => Corner::TopLeft,
+        };
+        PatternPanel {
+            board: game.board.clone(),
+            corner,
+            extent: CORNER_EXTENT.min(size),
+            search: None,
+            error: None,
+        }
+    }
+
+    pub fn pattern(&self) -> Pattern {
+        let region = Region::corner(self.corner, self.extent, self.board.size);
+        Pattern::from_board(&self.board, region)
+    }
+
+    pub fn next_corner(&mut self) {
+        self.corner = self.corner.next();
+        self.clear();
+    }
+
+    pub fn grow(&mut self) {
+        self.extent = (self.extent + 1).min(self.board.size);
+        self.clear();
+    }
+
+    pub fn shrink(&mut self) {
+        self.extent = self.extent.saturating_sub(1).max(2).min(self.board.size);
+        self.clear();
+    }
+
+    pub fn set_search(&mut self, search: PatternSearch) {
+        self.search = Some(search);
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    fn clear(&mut self) {
+        self.search = None;
+        self.error = None;
+    }
+}
+
+impl Overlay {
+    // Close `target` if the same kind of overlay is already open, otherwise switch to it
+    pub fn toggle(current: &mut Option<Overlay>, target: Overlay) {
+        let same_kind = current
+            .as_ref()
+            .is_some_and(|open| std::mem::discriminant(open) == std::mem::discriminant(&target));
+        *current = if same_kind { None } else { Some(target) };
+    }
+}
+
+// State of the file picker overlay: playlist entries narrowed by a typed filter
+pub struct FileBrowser {
+    summaries: Vec<GameSummary>,
+    filter: String,
+    selected: usize, // Position within the filtered entries
+    error: Option<String>,
+}
+
+impl FileBrowser {
+    pub fn new(playlist: &PlaylistManager) -> Self {
+        FileBrowser {
+            summaries: playlist.summaries(),
+            filter: String::new(),
+            selected: playlist.current_index(),
+            error: None,
+        }
+    }
+
+    // Playlist indices of the entries matching the filter
+    fn visible(&self) -> Vec<usize> {
+        self.summaries
+            .iter()
+            .enumerate()
+            .filter(|(_, summary)| summary.matches(&self.filter))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.visible().get(self.selected).copied()
+    }
+
+    pub fn push_filter(&mut self, ch: char) {
+        self.filter.push(ch);
+        self.selected = 0;
+        self.error = None;
+    }
+
+    pub fn pop_filter(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+        self.error = None;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.visible().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+}
+
+pub fn render_game(
+    frame: &mut Frame,
+    game: &GameState,
+    auto_play: bool,
+    playback_speed: u64,
+    playlist: &PlaylistManager,
+    live: bool,
+    search: Option<&GameSearch>,
+) {
+    let Some(layout) = game_layout(frame.area(), game) else {
+        render_too_small(frame, game);
+        return;
+    };
+
+    render_header(frame, layout.header, game, live, layout.style);
+    render_board(
+        frame,
+        layout.board,
+        &game.board,
+        game.rotation(),
+        layout.style,
+    );
+    render_status(
+        frame,
+        layout.status,
+        game,
+        auto_play,
+        playback_speed,
+        playlist,
+        layout.style,
+    );
+
+    // Search results go below the board, or over the status bar if there is no room
+    if let Some(search) = search {
+        let min_height = match layout.style {
+            BoardStyle::Regular => 3,
+            BoardStyle::Compact => 1,
+        };
+        let area = if layout.spare.height >= min_height {
+            layout.spare
+        } else {
+            layout.status
+        };
+        render_search_results(frame, area, game, search, layout.style);
+    }
+}
+
+fn regular_board_width(size: u16) -> u16 {
+    // Each intersection is 2 columns wide plus a 1-column connector
+    size * 3 - 1
+}
+
+// Returns None when the terminal is too small for even the compact board
+fn game_layout(area: Rect, game: &GameState) -> Option<GameLayout> {
+    let size = game.board.size as u16;
+
+    let style = if area.width >= regular_board_width(size) + 4 && area.height >= size + 8 {
+        BoardStyle::Regular
+    } else if area.width >= size && area.height >= size + 2 {
+        BoardStyle::Compact
+    } else {
+        return None;
+    };
+
+    let (bar_height, board_height) = match style {
+        BoardStyle::Regular => (3, size + 2), // Bordered bars, board + padding
+        BoardStyle::Compact => (1, size),
+    };
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),                // Header
-            Constraint::Length(board_height + 2), // Board + padding
-            Constraint::Min(0),                   // Fill remaining space
-            Constraint::Length(3),                // Status
+            Constraint::Length(bar_height),   // Header
+            Constraint::Length(board_height), // Board
+            Constraint::Min(0),               // Fill remaining space
+            Constraint::Length(bar_height),   // Status
         ])
-        .split(frame.area());
+        .split(area);
+
+    let board = match style {
+        BoardStyle::Regular => board_area(chunks[1]),
+        BoardStyle::Compact => Rect {
+            x: chunks[1].x + (chunks[1].width - size) / 2,
+            width: size,
+            ..chunks[1]
+        },
+    };
+
+    Some(GameLayout {
+        header: chunks[0],
+        board,
+        spare: chunks[2],
+        status: chunks[3],
+        style,
+    })
+}
+
+// Same offset ratatui uses when rendering a centered line
+fn centered_offset(line_width: u16, area_width: u16) -> u16 {
+    (area_width / 2).saturating_sub(line_width / 2)
+}
+
+// Board position (in board coordinates, not view coordinates) under a terminal cell
+pub fn board_point_at(area: Rect, game: &GameState, x: u16, y: u16) -> Option<(u8, u8)> {
+    let layout = game_layout(area, game)?;
+    let board_area = layout.board;
+    let size = game.board.size as u16;
+
+    let (left, cell_width) = match layout.style {
+        BoardStyle::Regular => {
+            let line_width = regular_board_width(size);
+            (
+                board_area.x + centered_offset(line_width, board_area.width),
+                3,
+            )
+        }
+        BoardStyle::Compact => (board_area.x, 1),
+    };
+
+    if x < left || x >= board_area.right() || y < board_area.y || y >= board_area.bottom() {
+        return None;
+    }
+
+    let view_col = (x - left) / cell_width;
+    let view_row = y - board_area.y;
+    if view_col >= size || view_row >= size {
+        return None;
+    }
+
+    let board_view = BoardView::new(&game.board, game.rotation());
+    Some(board_view.to_board(view_row as u8, view_col as u8))
+}
+
+pub fn status_control_at(
+    area: Rect,
+    game: &GameState,
+    auto_play: bool,
+    playback_speed: u64,
+    playlist: &PlaylistManager,
+    x: u16,
+    y: u16,
+) -> Option<StatusControl> {
+    let layout = game_layout(area, game)?;
+    let status_area = layout.status;
+
+    // Regular text sits on the line just below the top border
+    let text_row = match layout.style {
+        BoardStyle::Regular => status_area.y + 1,
+        BoardStyle::Compact => status_area.y,
+    };
+    if y != text_row {
+        return None;
+    }
+
+    let spans = status_spans(game, auto_play, playback_speed, playlist);
+    let line_width: u16 = spans.iter().map(|(span, _)| span.width() as u16).sum();
+    let mut span_x = status_area.x + centered_offset(line_width, status_area.width);
+
+    for (span, control) in spans {
+        let width = span.width() as u16;
+        if x >= span_x && x < span_x + width {
+            return control;
+        }
+        span_x += width;
+    }
+
+    None
+}
+
+fn render_too_small(frame: &mut Frame, game: &GameState) {
+    let area = frame.area();
+    let size = game.board.size as u16;
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(RatatuiColor::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("Need {}x{}", size, size + 2),
+            Style::default().fg(RatatuiColor::DarkGray),
+        )),
+        Line::from(Span::styled(
+            format!("Have {}x{}", area.width, area.height),
+            Style::default().fg(RatatuiColor::DarkGray),
+        )),
+    ];
+
+    // Vertically center the message when there is room for it
+    let top_offset = area.height.saturating_sub(3) / 2;
+    let message_area = Rect {
+        y: area.y + top_offset,
+        height: area.height - top_offset,
+        ..area
+    };
 
-    render_header(frame, chunks[0], game);
-    render_board(frame, chunks[1], &game.board, game.rotation());
-    render_status(frame, chunks[3], game, auto_play, playback_speed);
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, message_area);
 }
 
-fn render_header(frame: &mut Frame, area: Rect, game: &GameState) {
+fn render_header(frame: &mut Frame, area: Rect, game: &GameState, live: bool, style: BoardStyle) {
     let black_player = game.get_property("PB").unwrap_or("Black");
     let white_player = game.get_property("PW").unwrap_or("White");
     let game_name = game.get_property("GN").unwrap_or("Go Game");
@@ -46,6 +474,14 @@ fn render_header(frame: &mut Frame, area: Rect, game: &GameState) {
     let center_color = RatatuiColor::Rgb(255, 240, 210); // Very bright center
 
     let mut title_spans = Vec::new();
+    if live {
+        title_spans.push(Span::styled(
+            "● LIVE ",
+            Style::default()
+                .fg(RatatuiColor::Red)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
     let chars: Vec<char> = game_name.chars().collect();
     let speed = 150; // milliseconds per character
 
@@ -101,16 +537,20 @@ fn render_header(frame: &mut Frame, area: Rect, game: &GameState) {
 
     let text = Line::from(title_spans);
 
+    let borders = match style {
+        BoardStyle::Regular => Borders::BOTTOM,
+        BoardStyle::Compact => Borders::NONE,
+    };
     let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
         Block::default()
-            .borders(Borders::BOTTOM)
+            .borders(borders)
             .border_style(Style::default().fg(RatatuiColor::DarkGray)),
     );
 
     frame.render_widget(paragraph, area);
 }
 
-fn render_board(frame: &mut Frame, area: Rect, board: &Board, rotation: u8) {
+fn render_board(frame: &mut Frame, area: Rect, board: &Board, rotation: u8, style: BoardStyle) {
     let board_view = BoardView::new(board, rotation);
     let size = board_view.size() as usize;
     let mut lines = Vec::new();
@@ -119,59 +559,71 @@ fn render_board(frame: &mut Frame, area: Rect, board: &Board, rotation: u8) {
         let mut spans = Vec::new();
 
         for col in 0..size {
-            match board_view.get(row as u8, col as u8) {
-                Some(Color::Black) => {
-                    // Black stone emoji - naturally takes 2 char widths
-                    spans.push(Span::styled("⚫", Style::default()));
-                }
-                Some(Color::White) => {
-                    // White stone emoji - naturally takes 2 char widths
-                    spans.push(Span::styled("⚪", Style::default()));
-                }
-                None => {
-                    // Draw intersection in dark gray (toned down)
-                    // Use 2 characters for each intersection to match emoji width
-                    let intersection = if row == 0 && col == 0 {
-                        "┌─"
-                    } else if row == 0 && col == size - 1 {
-                        "─┐"
-                    } else if row == size - 1 && col == 0 {
-                        "└─"
-                    } else if row == size - 1 && col == size - 1 {
-                        "─┘"
-                    } else if row == 0 {
-                        "─┬"
-                    } else if row == size - 1 {
-                        "─┴"
-                    } else if col == 0 {
-                        "├─"
-                    } else if col == size - 1 {
-                        "─┤"
-                    } else {
-                        "─┼"
-                    };
-                    spans.push(Span::styled(
-                        intersection,
-                        Style::default().fg(RatatuiColor::DarkGray),
-                    ));
-                }
-            }
+            let stone = board_view.get(row as u8, col as u8);
+            match style {
+                BoardStyle::Regular => {
+                    spans.push(regular_cell(stone, row, col, size));
 
-            // Add horizontal line between intersections (except last column)
-            if col < size - 1 {
-                spans.push(Span::styled(
-                    "─",
-                    Style::default().fg(RatatuiColor::DarkGray),
-                ));
+                    // Add horizontal line between intersections (except last column)
+                    if col < size - 1 {
+                        spans.push(Span::styled(
+                            "─",
+                            Style::default().fg(RatatuiColor::DarkGray),
+                        ));
+                    }
+                }
+                BoardStyle::Compact => spans.push(compact_cell(stone, row, col, size)),
             }
         }
 
         lines.push(Line::from(spans));
     }
 
+    let alignment = match style {
+        BoardStyle::Regular => Alignment::Center,
+        BoardStyle::Compact => Alignment::Left,
+    };
+    let paragraph = Paragraph::new(lines).alignment(alignment).block(
+        Block::default().style(Style::default().bg(RatatuiColor::Rgb(210, 180, 140))), // Tan wood color
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+fn regular_cell(stone: Option<Color>, row: usize, col: usize, size: usize) -> Span<'static> {
+    match stone {
+        // Stone emoji - naturally takes 2 char widths
+        Some(Color::Black) => Span::styled(BLACK_STONE, Style::default()),
+        Some(Color::White) => Span::styled(WHITE_STONE, Style::default()),
+        None => {
+            // Draw intersection in dark gray (toned down)
+            let intersection = Intersection::at(row as u8, col as u8, size as u8);
+            Span::styled(
+                intersection.regular_glyph(),
+                Style::default().fg(RatatuiColor::DarkGray),
+            )
+        }
+    }
+}
+
+fn compact_cell(stone: Option<Color>, row: usize, col: usize, size: usize) -> Span<'static> {
+    match stone {
+        // Single-width glyphs so each intersection takes one column
+        Some(Color::Black) => Span::styled("●", Style::default().fg(RatatuiColor::Black)),
+        Some(Color::White) => Span::styled("●", Style::default().fg(RatatuiColor::White)),
+        None => {
+            let intersection = Intersection::at(row as u8, col as u8, size as u8);
+            Span::styled(
+                intersection.compact_glyph(),
+                Style::default().fg(RatatuiColor::DarkGray),
+            )
+        }
+    }
+}
+
+fn board_area(area: Rect) -> Rect {
     // Add padding around the board to create a "board on table" effect
     // Limit board dimensions, center it if window is larger
-    use ratatui::layout::Margin;
     let max_board_width = 65;
     let max_board_height = 27;
 
@@ -187,16 +639,10 @@ fn render_board(frame: &mut Frame, area: Rect, board: &Board, rotation: u8) {
         1 // Minimum padding
     };
 
-    let board_area = area.inner(Margin {
+    area.inner(Margin {
         horizontal: horizontal_padding,
         vertical: vertical_padding,
-    });
-
-    let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
-        Block::default().style(Style::default().bg(RatatuiColor::Rgb(210, 180, 140))), // Tan wood color
-    );
-
-    frame.render_widget(paragraph, board_area);
+    })
 }
 
 fn render_status(
@@ -205,13 +651,45 @@ fn render_status(
     game: &GameState,
     auto_play: bool,
     playback_speed: u64,
+    playlist: &PlaylistManager,
+    style: BoardStyle,
 ) {
+    let spans: Vec<Span> = status_spans(game, auto_play, playback_speed, playlist)
+        .into_iter()
+        .map(|(span, _)| span)
+        .collect();
+
+    let borders = match style {
+        BoardStyle::Regular => Borders::TOP,
+        BoardStyle::Compact => Borders::NONE,
+    };
+    let paragraph = Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(borders)
+                .border_style(Style::default().fg(RatatuiColor::DarkGray)),
+        );
+
+    frame.render_widget(paragraph, area);
+}
+
+// Status bar spans, each tagged with the control it toggles when clicked
+fn status_spans(
+    game: &GameState,
+    auto_play: bool,
+    playback_speed: u64,
+    playlist: &PlaylistManager,
+) -> Vec<(Span<'static>, Option<StatusControl>)> {
     let mut spans = Vec::new();
 
     // Move counter
-    spans.push(Span::styled(
-        format!("Move {}/{}", game.current_move, game.moves.len()),
-        Style::default().fg(RatatuiColor::Cyan),
+    spans.push((
+        Span::styled(
+            format!("Move {}/{}", game.current_move, game.moves.len()),
+            Style::default().fg(RatatuiColor::Cyan),
+        ),
+        None,
     ));
 
     // Current move info
@@ -229,35 +707,49 @@ fn render_status(
         } else {
             "Pass".to_string()
         };
-        spans.push(Span::raw(" | "));
-        spans.push(Span::styled(format!("{} {}", color_text, pos), color_style));
+        spans.push((Span::raw(" | "), None));
+        spans.push((
+            Span::styled(format!("{} {}", color_text, pos), color_style),
+            None,
+        ));
     }
 
     // Play status with emoji
-    spans.push(Span::raw(" "));
-    if auto_play {
-        spans.push(Span::styled(
+    spans.push((Span::raw(" "), None));
+    let play_span = if auto_play {
+        Span::styled(
             "▶️", // Play button emoji
             Style::default().fg(RatatuiColor::Green),
-        ));
+        )
     } else {
-        spans.push(Span::styled(
+        Span::styled(
             "⏸️", // Pause button emoji
             Style::default().fg(RatatuiColor::Yellow),
-        ));
-    }
+        )
+    };
+    spans.push((play_span, Some(StatusControl::PlayPause)));
 
-    // Loop status with emoji
-    spans.push(Span::raw(" "));
-    if game.is_looping_enabled() {
-        spans.push(Span::styled(
-            "🔁", // Repeat/loop emoji
+    // Repeat mode with emoji
+    spans.push((Span::raw(" "), None));
+    let loop_span = match playlist.repeat() {
+        RepeatMode::All => Span::styled(
+            "🔁", // Repeat all emoji
             Style::default().fg(RatatuiColor::Magenta),
-        ));
-    } else {
-        spans.push(Span::styled(
-            "➡️", // Right arrow emoji (no loop, just forward)
+        ),
+        RepeatMode::One => Span::styled(
+            "🔂", // Repeat one emoji
+            Style::default().fg(RatatuiColor::Magenta),
+        ),
+        RepeatMode::Off => Span::styled(
+            "➡️", // Right arrow emoji (no repeat, just forward)
             Style::default().fg(RatatuiColor::DarkGray),
+        ),
+    };
+    spans.push((loop_span, Some(StatusControl::Loop)));
+    if playlist.shuffle_seed().is_some() {
+        spans.push((
+            Span::styled("🔀", Style::default().fg(RatatuiColor::Magenta)),
+            None,
         ));
     }
 
@@ -304,30 +796,750 @@ fn render_status(
         Span::styled("·", Style::default().fg(RatatuiColor::DarkGray))
     };
 
-    spans.push(Span::raw(" "));
-    spans.push(Span::styled(
-        format!("{}x ", playback_speed),
-        Style::default().fg(RatatuiColor::Cyan),
+    spans.push((Span::raw(" "), None));
+    spans.push((
+        Span::styled(
+            format!("{}x ", playback_speed),
+            Style::default().fg(RatatuiColor::Cyan),
+        ),
+        Some(StatusControl::Speed),
     ));
-    spans.push(star); // Add the animated star Span directly
+    spans.push((star, Some(StatusControl::Speed))); // Add the animated star Span directly
 
-    // Controls
-    spans.push(Span::styled(
-        " | ← → Step | Space Play/Pause | L Loop | S Speed | Q Quit",
-        Style::default().fg(RatatuiColor::DarkGray),
+    // Controls (each hint is clickable)
+    let hint_style = Style::default().fg(RatatuiColor::DarkGray);
+    spans.push((Span::styled(" | ? Help | ← → Step | ", hint_style), None));
+    spans.push((
+        Span::styled("Space Play/Pause", hint_style),
+        Some(StatusControl::PlayPause),
+    ));
+    spans.push((Span::styled(" | ", hint_style), None));
+    spans.push((
+        Span::styled("L Repeat", hint_style),
+        Some(StatusControl::Loop),
+    ));
+    spans.push((Span::styled(" | ", hint_style), None));
+    spans.push((
+        Span::styled("S Speed", hint_style),
+        Some(StatusControl::Speed),
     ));
+    spans.push((Span::styled(" | Q Quit", hint_style), None));
 
-    let paragraph = Paragraph::new(Line::from(spans))
+    spans
+}
+
+// Every binding shown in the help overlay: (keys, action)
+const HELP_BINDINGS: &[(&str, &str)] = &[
+    ("← / →", "Step backward/forward"),
+    ("Home / End", "Jump to start/end of game"),
+    ("Space", "Toggle auto-play"),
+    ("N / P", "Next/previous game (also PgDn/PgUp)"),
+    ("L", "Cycle repeat: all, one, off"),
+    ("R", "Toggle shuffle"),
+    ("S", "Cycle playback speed"),
+    ("I", "Toggle game info panel"),
+    ("O", "Open file browser (type to filter)"),
+    ("M", "Bookmark this position (with a label)"),
+    ("B", "List bookmarks"),
+    ("/", "Search comments, names and labels"),
+    ("J", "Look for a corner pattern in the playlist"),
+    ("n / N", "Next/previous match while searching"),
+    ("?", "Toggle this help"),
+    ("Q / Esc", "Quit (Esc closes overlays and search first)"),
+    ("Scroll wheel", "Step backward/forward"),
+    ("Click stone", "Jump to the move that placed it"),
+    ("Click status", "Toggle play, repeat or speed"),
+];
+
+pub fn render_help(
+    frame: &mut Frame,
+    game: &GameState,
+    auto_play: bool,
+    playback_speed: u64,
+    playlist: &PlaylistManager,
+) {
+    let key_style = Style::default().fg(RatatuiColor::Cyan);
+    let text_style = Style::default().fg(RatatuiColor::White);
+    let heading_style = Style::default()
+        .fg(RatatuiColor::Rgb(255, 190, 140))
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from(Span::styled("Controls", heading_style))];
+    for (keys, action) in HELP_BINDINGS {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", keys), key_style),
+            Span::styled(*action, text_style),
+        ]));
+    }
+
+    let settings = [
+        (
+            "Playback",
+            if auto_play { "Playing" } else { "Paused" }.to_string(),
+        ),
+        ("Speed", format!("{}x", playback_speed)),
+        (
+            "Repeat",
+            match playlist.repeat() {
+                RepeatMode::All => "All",
+                RepeatMode::One => "One",
+                RepeatMode::Off => "Off",
+            }
+            .to_string(),
+        ),
+        (
+            "Shuffle",
+            match playlist.shuffle_seed() {
+                Some(seed) => format!("On (--seed {})", seed),
+                None => "Off".to_string(),
+            },
+        ),
+        ("Rotation", format!("{}°", game.rotation() as u16 * 90)),
+        ("Theme", "Wood".to_string()),
+        (
+            "Game",
+            format!(
+                "{} of {}",
+                playlist.current_index() + 1,
+                playlist.files().len()
+            ),
+        ),
+        ("File", playlist.current().display().to_string()),
+    ];
+
+    lines.push(Line::raw(""));
+    lines.push(Line::from(Span::styled("Settings", heading_style)));
+    for (name, value) in settings {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", name), key_style),
+            Span::styled(value, text_style),
+        ]));
+    }
+
+    let height = lines.len() as u16 + 2; // + borders
+    let area = centered_rect(frame.area(), 60, height);
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Help (Esc to close) ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(RatatuiColor::DarkGray))
+            .style(Style::default().bg(RatatuiColor::Black)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_info(frame: &mut Frame, game: &GameState, note: Option<&str>) {
+    let name_style = Style::default().fg(RatatuiColor::Cyan);
+    let text_style = Style::default().fg(RatatuiColor::White);
+    let heading_style = Style::default()
+        .fg(RatatuiColor::Rgb(255, 190, 140))
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = Vec::new();
+    if let Some(note) = note {
+        lines.push(Line::from(Span::styled("Playlist note", heading_style)));
+        lines.push(Line::from(Span::styled(format!("  {}", note), text_style)));
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::from(Span::styled("Game", heading_style)));
+    let known = game.known_properties();
+    if known.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No game information recorded",
+            Style::default().fg(RatatuiColor::DarkGray),
+        )));
+    }
+    for (name, value) in known {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", name), name_style),
+            Span::styled(value, text_style),
+        ]));
+    }
+
+    let raw = game.unknown_properties();
+    if !raw.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled("Other properties", heading_style)));
+        for (key, value) in raw {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<14}", key), name_style),
+                Span::styled(value, text_style),
+            ]));
+        }
+    }
+
+    let height = lines.len() as u16 + 2; // + borders
+    let area = centered_rect(frame.area(), 70, height);
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Game Info (Esc to close) ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(RatatuiColor::DarkGray))
+            .style(Style::default().bg(RatatuiColor::Black)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_browser(frame: &mut Frame, browser: &FileBrowser, playing_index: usize) {
+    let area = centered_rect(frame.area(), 100, frame.area().height.saturating_sub(4));
+
+    let block = Block::default()
+        .title(" Open Game (Enter to open, Esc to close) ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(RatatuiColor::DarkGray))
+        .style(Style::default().bg(RatatuiColor::Black));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Filter prompt or error
+            Constraint::Min(0),    // Entries
+        ])
+        .split(inner);
+
+    let prompt = match &browser.error {
+        Some(error) => Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(RatatuiColor::Red),
+        )),
+        None => Line::from(vec![
+            Span::styled("Filter: ", Style::default().fg(RatatuiColor::Cyan)),
+            Span::styled(
+                format!("{}_", browser.filter),
+                Style::default().fg(RatatuiColor::White),
+            ),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(prompt), chunks[0]);
+
+    let visible = browser.visible();
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|&index| {
+            let summary = &browser.summaries[index];
+            let marker = if index == playing_index { "• " } else { "  " };
+            let players = format!(
+                "{} vs {}",
+                summary.black.as_deref().unwrap_or("?"),
+                summary.white.as_deref().unwrap_or("?")
+            );
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(RatatuiColor::Green)),
+                Span::styled(
+                    format!("{:<28} ", summary.file_name()),
+                    Style::default().fg(RatatuiColor::White),
+                ),
+                Span::styled(
+                    format!("{:<36} ", players),
+                    Style::default().fg(RatatuiColor::Rgb(255, 190, 140)),
+                ),
+                Span::styled(
+                    format!("{:<12} ", summary.date.as_deref().unwrap_or("")),
+                    Style::default().fg(RatatuiColor::DarkGray),
+                ),
+                Span::styled(
+                    summary.result.clone().unwrap_or_default(),
+                    Style::default().fg(RatatuiColor::Cyan),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(RatatuiColor::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut state = ListState::default().with_selected(Some(browser.selected));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+pub fn render_resume(frame: &mut Frame, offer: &ResumeOffer, seconds_left: u64) {
+    let key_style = Style::default().fg(RatatuiColor::Cyan);
+    let text_style = Style::default().fg(RatatuiColor::White);
+    let lines = vec![
+        Line::from(Span::styled(
+            if offer.move_number > 0 {
+                format!("Continue {} at move {}?", offer.name, offer.move_number)
+            } else {
+                format!("Continue with {}?", offer.name)
+            },
+            text_style,
+        )),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled("Enter / Y", key_style),
+            Span::styled(" resume   ", text_style),
+            Span::styled("Esc / N", key_style),
+            Span::styled(" start over", text_style),
+        ]),
+        Line::from(Span::styled(
+            format!("Starting over in {}s", seconds_left),
+            Style::default().fg(RatatuiColor::DarkGray),
+        )),
+    ];
+
+    let area = centered_rect(frame.area(), 60, lines.len() as u16 + 2);
+    let paragraph = Paragraph::new(lines)
         .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
         .block(
             Block::default()
-                .borders(Borders::TOP)
-                .border_style(Style::default().fg(RatatuiColor::DarkGray)),
+                .title(" Resume ")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(RatatuiColor::DarkGray))
+                .style(Style::default().bg(RatatuiColor::Black)),
         );
 
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_label_prompt(frame: &mut Frame, prompt: &LabelPrompt) {
+    let lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "Bookmark move {} of {}",
+                prompt.move_number,
+                prompt
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            ),
+            Style::default().fg(RatatuiColor::White),
+        )),
+        Line::from(vec![
+            Span::styled("Label: ", Style::default().fg(RatatuiColor::Cyan)),
+            Span::styled(
+                format!("{}_", prompt.text),
+                Style::default().fg(RatatuiColor::White),
+            ),
+        ]),
+    ];
+
+    let area = centered_rect(frame.area(), 60, lines.len() as u16 + 2);
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Add Bookmark (Enter to save, Esc to cancel) ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(RatatuiColor::DarkGray))
+            .style(Style::default().bg(RatatuiColor::Black)),
+    );
+
+    frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
 }
 
+pub fn render_bookmarks(frame: &mut Frame, list: &BookmarkList) {
+    let height = (list.bookmarks.len() as u16 + 3).max(5);
+    let area = centered_rect(frame.area(), 80, height);
+
+    let block = Block::default()
+        .title(" Bookmarks (Enter to open, D to delete, Esc to close) ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(RatatuiColor::DarkGray))
+        .style(Style::default().bg(RatatuiColor::Black));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Error, if any
+            Constraint::Min(0),    // Entries
+        ])
+        .split(inner);
+
+    if let Some(error) = &list.error {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                error.clone(),
+                Style::default().fg(RatatuiColor::Red),
+            )),
+            chunks[0],
+        );
+    }
+
+    if list.bookmarks.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "  No bookmarks yet: press M to bookmark a position",
+                Style::default().fg(RatatuiColor::DarkGray),
+            )),
+            chunks[1],
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = list
+        .bookmarks
+        .iter()
+        .map(|bookmark| {
+            let file_name = bookmark
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {:<36} ", bookmark.label.as_deref().unwrap_or("")),
+                    Style::default().fg(RatatuiColor::Rgb(255, 190, 140)),
+                ),
+                Span::styled(
+                    format!("{:<28} ", file_name),
+                    Style::default().fg(RatatuiColor::White),
+                ),
+                Span::styled(
+                    format!("move {}", bookmark.move_number),
+                    Style::default().fg(RatatuiColor::Cyan),
+                ),
+            ]))
+        })
+        .collect();
+
+    let highlighted = List::new(items).highlight_style(
+        Style::default()
+            .bg(RatatuiColor::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut state = ListState::default().with_selected(Some(list.selected));
+    frame.render_stateful_widget(highlighted, chunks[1], &mut state);
+}
+
+pub fn render_search_prompt(frame: &mut Frame, prompt: &SearchPrompt, game: &GameState) {
+    let found = GameSearch::new(game, &prompt.text).matches().len();
+    let count = match (prompt.text.trim().is_empty(), found) {
+        (true, _) => String::new(),
+        (false, 0) => "No matches".to_string(),
+        (false, 1) => "1 matching move".to_string(),
+        (false, n) => format!("{} matching moves", n),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("/", Style::default().fg(RatatuiColor::Cyan)),
+            Span::styled(
+                format!("{}_", prompt.text),
+                Style::default().fg(RatatuiColor::White),
+            ),
+        ]),
+        Line::from(Span::styled(
+            count,
+            Style::default().fg(RatatuiColor::DarkGray),
+        )),
+    ];
+
+    let area = centered_rect(frame.area(), 60, lines.len() as u16 + 2);
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Search comments, names and labels (Enter to search) ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(RatatuiColor::DarkGray))
+            .style(Style::default().bg(RatatuiColor::Black)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_search_results(
+    frame: &mut Frame,
+    area: Rect,
+    game: &GameState,
+    search: &GameSearch,
+    style: BoardStyle,
+) {
+    let total = search.matches().len();
+    let position = match (search.position(game.current_move), total) {
+        (_, 0) => " no matches".to_string(),
+        (Some(index), _) => format!(" match {} of {}", index + 1, total),
+        (None, _) => format!(" {} matches", total),
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("/{}", search.query()),
+            Style::default()
+                .fg(RatatuiColor::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(position, Style::default().fg(RatatuiColor::Cyan)),
+        Span::styled(
+            "  n/N next/previous, Esc clears",
+            Style::default().fg(RatatuiColor::DarkGray),
+        ),
+    ])];
+
+    match search.at(game.current_move) {
+        Some(found) => {
+            for field in &found.fields {
+                let mut spans = vec![Span::styled(
+                    format!("{}: ", field.field.name()),
+                    Style::default().fg(RatatuiColor::Cyan),
+                )];
+                spans.extend(highlighted_spans(field));
+                lines.push(Line::from(spans));
+            }
+        }
+        None => {
+            if let Some(next) = search.next_after(game.current_move) {
+                lines.push(Line::from(Span::styled(
+                    format!("Next match at move {}", next),
+                    Style::default().fg(RatatuiColor::DarkGray),
+                )));
+            }
+        }
+    }
+
+    let borders = match style {
+        BoardStyle::Regular => Borders::TOP,
+        BoardStyle::Compact => Borders::NONE,
+    };
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(RatatuiColor::DarkGray)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+// Characters of context kept before the first match of a long text
+const SEARCH_CONTEXT: usize = 20;
+
+// The matched text on one line with each match highlighted
+fn highlighted_spans(found: &FieldMatch) -> Vec<Span<'static>> {
+    // ASCII line breaks and tabs become spaces, which keeps the byte ranges valid
+    let text: String = found
+        .text
+        .chars()
+        .map(|ch| if ch.is_ascii_whitespace() { ' ' } else { ch })
+        .collect();
+    let plain = Style::default().fg(RatatuiColor::White);
+    let highlight = Style::default()
+        .fg(RatatuiColor::Black)
+        .bg(RatatuiColor::Yellow);
+
+    let first = found.ranges.first().map_or(0, |range| range.start);
+    let start = text[..first]
+        .char_indices()
+        .rev()
+        .nth(SEARCH_CONTEXT - 1)
+        .map_or(0, |(index, _)| index);
+
+    let mut spans = Vec::new();
+    if start > 0 {
+        spans.push(Span::styled("…", plain));
+    }
+    let mut shown = start;
+    for range in &found.ranges {
+        spans.push(Span::styled(text[shown..range.start].to_string(), plain));
+        spans.push(Span::styled(text[range.clone()].to_string(), highlight));
+        shown = range.end;
+    }
+    spans.push(Span::styled(text[shown..].to_string(), plain));
+    spans
+}
+
+pub fn render_pattern(frame: &mut Frame, panel: &PatternPanel) {
+    let pattern = panel.pattern();
+    let region = pattern.region();
+    let size = pattern.size();
+    let continuations = panel
+        .search
+        .as_ref()
+        .map(|search| search.continuations())
+        .unwrap_or_default();
+
+    // Points where the region was continued are lettered A, B, C... on the
+    // diagram, most played first; both colours at a point share its letter
+    let mut lettered: Vec<(u8, u8)> = Vec::new();
+    for (continuation, _) in &continuations {
+        if let Some(continuation) = continuation {
+            if !lettered.contains(&continuation.position) && lettered.len() < 26 {
+                lettered.push(continuation.position);
+            }
+        }
+    }
+    let letter = |point: (u8, u8)| {
+        lettered
+            .iter()
+            .position(|&at| at == point)
+            .map(|index| (b'A' + index as u8) as char)
+    };
+
+    let height = (region.height() as u16).max(continuations.len() as u16 + 3) + 3;
+    let area = centered_rect(frame.area(), 76, height);
+    let block = Block::default()
+        .title(" Pattern Search (Tab corner, +/- size, Enter search, Esc close) ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(RatatuiColor::DarkGray))
+        .style(Style::default().bg(RatatuiColor::Black));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Region and summary
+            Constraint::Min(0),    // Diagram and continuations
+        ])
+        .split(inner);
+
+    let stones = pattern.stones();
+    let summary = match (&panel.error, &panel.search) {
+        (Some(error), _) => Span::styled(error.clone(), Style::default().fg(RatatuiColor::Red)),
+        (None, Some(search)) => Span::styled(
+            format!(
+                "Found {} times in {} of {} games",
+                search.hits().len(),
+                search.matching_games(),
+                search.games()
+            ),
+            Style::default().fg(RatatuiColor::Cyan),
+        ),
+        (None, None) => Span::styled(
+            "Enter looks for it in every game of the playlist",
+            Style::default().fg(RatatuiColor::DarkGray),
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!(
+                    "{} {}x{} ({}), {} stone{}",
+                    panel.corner.name(),
+                    region.width(),
+                    region.height(),
+                    region.name(size),
+                    stones,
+                    if stones == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(RatatuiColor::White),
+            )),
+            Line::from(summary),
+        ]),
+        rows[0],
+    );
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(region.width() as u16 + 3), // Diagram
+            Constraint::Min(0),                            // Continuations
+        ])
+        .split(rows[1]);
+
+    let diagram: Vec<Line> = (region.top..=region.bottom)
+        .map(|row| {
+            let spans: Vec<Span> = (region.left..=region.right)
+                .map(|col| match letter((row, col)) {
+                    Some(letter) => Span::styled(
+                        letter.to_string(),
+                        Style::default()
+                            .fg(RatatuiColor::Red)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    None => compact_cell(
+                        pattern.get((row, col)),
+                        row as usize,
+                        col as usize,
+                        size as usize,
+                    ),
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    let diagram_area = Rect {
+        width: region.width() as u16,
+        height: region.height() as u16,
+        ..columns[0]
+    }
+    .intersection(columns[0]);
+    frame.render_widget(
+        Paragraph::new(diagram)
+            .block(Block::default().style(Style::default().bg(RatatuiColor::Rgb(210, 180, 140)))),
+        diagram_area,
+    );
+
+    if continuations.is_empty() {
+        return;
+    }
+    let mut lines = vec![Line::from(Span::styled(
+        "   Next move          Times",
+        Style::default().fg(RatatuiColor::Cyan),
+    ))];
+    for (continuation, count) in &continuations {
+        let (letter, text) = match continuation {
+            Some(continuation) => (
+                letter(continuation.position).unwrap_or(' '),
+                format!(
+                    "{} {}",
+                    match continuation.color {
+                        Color::Black => "Black",
+                        Color::White => "White",
+                    },
+                    point_name(continuation.position, size)
+                ),
+            ),
+            None => (' ', "Not played again".to_string()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}  ", letter),
+                Style::default()
+                    .fg(RatatuiColor::Red)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{:<18} ", text),
+                Style::default().fg(RatatuiColor::White),
+            ),
+            Span::styled(
+                format!("{:>5}", count),
+                Style::default().fg(RatatuiColor::Cyan),
+            ),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), columns[1]);
+}
+
+// Rect of at most width x height, centered within area
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
 pub fn render_transition(frame: &mut Frame, to_title: &str, elapsed: std::time::Duration) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,174 @@
+use smartgameviewer::archive::{
+    is_archive, list_entries, read_entry, split_entry_path, ArchiveCache,
+};
+use smartgameviewer::game::load_game_from_path;
+use smartgameviewer::playlist::{GameSummary, PlaylistManager};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const GAME: &str = "(;SZ[19]PB[Black]PW[White];B[dd];W[pp])";
+
+// Entries deliberately out of natural order, plus a non-game file
+const ENTRIES: &[&str] = &["game10.sgf", "round2/game1.sgf", "game2.sgf", "readme.txt"];
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn game_named(name: &str) -> String {
+    GAME.replace("SZ[19]", &format!("SZ[19]GN[{}]", name))
+}
+
+fn write_zip(path: &Path) {
+    let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    for name in ENTRIES {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(game_named(name).as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn write_tar_gz(path: &Path) {
+    let encoder =
+        flate2::write::GzEncoder::new(File::create(path).unwrap(), flate2::Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+    for name in ENTRIES {
+        let data = game_named(name);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, format!("./{}", name), data.as_bytes())
+            .unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+}
+
+fn entry_names(archive: &Path, files: &[PathBuf]) -> Vec<String> {
+    files
+        .iter()
+        .map(|file| {
+            file.strip_prefix(archive)
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_is_archive() {
+    assert!(is_archive(Path::new("games.zip")));
+    assert!(is_archive(Path::new("games.TAR.GZ")));
+    assert!(is_archive(Path::new("games.tgz")));
+    assert!(!is_archive(Path::new("games.sgf")));
+    assert!(!is_archive(Path::new("games.gz")));
+}
+
+#[test]
+fn test_zip_playlist() {
+    let dir = temp_dir("smartgameviewer_zip_test");
+    let archive = dir.join("games.zip");
+    write_zip(&archive);
+
+    let listed = list_entries(&archive).unwrap();
+    assert_eq!(listed.len(), 3);
+
+    // Natural sort by the entry name inside the archive
+    let playlist = PlaylistManager::new(archive.to_str()).unwrap();
+    assert_eq!(
+        entry_names(&archive, playlist.files()),
+        vec!["game2.sgf", "game10.sgf", "round2/game1.sgf"]
+    );
+
+    let entry = &playlist.files()[2];
+    let (found, name) = split_entry_path(entry).unwrap();
+    assert_eq!(found, archive.as_path());
+    assert_eq!(name, "round2/game1.sgf");
+
+    let game = load_game_from_path(entry).unwrap();
+    assert_eq!(game.get_property("GN"), Some("round2/game1.sgf"));
+    assert_eq!(game.moves.len(), 2);
+    assert_eq!(GameSummary::load(entry).black.as_deref(), Some("Black"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_tar_gz_playlist() {
+    let dir = temp_dir("smartgameviewer_tar_test");
+    let archive = dir.join("games.tar.gz");
+    write_tar_gz(&archive);
+
+    let playlist = PlaylistManager::new(archive.to_str()).unwrap();
+    assert_eq!(
+        entry_names(&archive, playlist.files()),
+        vec!["game2.sgf", "game10.sgf", "round2/game1.sgf"]
+    );
+
+    // "./" prefixes in the tar are ignored when looking entries up
+    let game = load_game_from_path(&playlist.files()[1]).unwrap();
+    assert_eq!(game.get_property("GN"), Some("game10.sgf"));
+    assert!(read_entry(&archive, "missing.sgf").is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_tar_gz_reread_after_change() {
+    let dir = temp_dir("smartgameviewer_tar_change_test");
+    let archive = dir.join("games.tgz");
+    let other = dir.join("other.tar.gz");
+    write_tar_gz(&archive);
+    write_tar_gz(&other);
+
+    // Each bundle keeps its own entry, so alternating between them is fine
+    let mut cache = ArchiveCache::default();
+    assert_eq!(cache.list_entries(&archive).unwrap().len(), 3);
+    assert_eq!(cache.list_entries(&other).unwrap().len(), 3);
+    let before = cache.read_entry(&archive, "game2.sgf").unwrap();
+    assert_eq!(before, game_named("game2.sgf").as_bytes());
+
+    // Replaced by a smaller bundle: the cached entries must not be served
+    let encoder = flate2::write::GzEncoder::new(
+        File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut tar = tar::Builder::new(encoder);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(GAME.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, "game2.sgf", GAME.as_bytes())
+        .unwrap();
+    tar.into_inner().unwrap().finish().unwrap();
+
+    assert_eq!(cache.list_entries(&archive).unwrap().len(), 1);
+    assert_eq!(
+        cache.read_entry(&archive, "game2.sgf").unwrap(),
+        GAME.as_bytes()
+    );
+    assert!(cache.read_entry(&archive, "game10.sgf").is_err());
+    assert!(cache.read_entry(&other, "game10.sgf").is_ok());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_archive_without_games() {
+    let dir = temp_dir("smartgameviewer_empty_zip_test");
+    let archive = dir.join("empty.zip");
+    let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+    zip.start_file("notes.txt", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    zip.finish().unwrap();
+
+    assert!(PlaylistManager::new(archive.to_str()).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
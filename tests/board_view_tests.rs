@@ -99,3 +99,37 @@ fn test_board_view_non_19x19() {
     assert_eq!(view2.get(8, 8), Some(Color::Black)); // (0,0) -> (8,8)
     assert_eq!(view2.get(0, 0), Some(Color::White)); // (8,8) -> (0,0)
 }
+
+#[test]
+fn test_to_board_inverts_rotation() {
+    let mut board = Board::new(19);
+    board.set(3, 5, Color::Black);
+
+    for rotation in 0..4 {
+        let view = BoardView::new(&board, rotation);
+        for row in 0..19 {
+            for col in 0..19 {
+                let (board_row, board_col) = view.to_board(row, col);
+                assert_eq!(view.get(row, col), board.get(board_row, board_col));
+            }
+        }
+    }
+
+    // 90° view (13, 3) shows board (3, 5)
+    assert_eq!(BoardView::new(&board, 1).to_board(13, 3), (3, 5));
+}
+
+#[test]
+fn test_to_view_inverts_to_board() {
+    let board = Board::new(13);
+
+    for rotation in 0..4 {
+        let view = BoardView::new(&board, rotation);
+        for row in 0..13 {
+            for col in 0..13 {
+                let (board_row, board_col) = view.to_board(row, col);
+                assert_eq!(view.to_view(board_row, board_col), (row, col));
+            }
+        }
+    }
+}
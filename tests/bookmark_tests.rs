@@ -0,0 +1,82 @@
+use smartgameviewer::bookmarks::{Bookmark, BookmarkStore};
+use smartgameviewer::playlist::PlaylistManager;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_bookmarks_round_trip() {
+    let dir = std::env::temp_dir().join("smartgameviewer_bookmarks_test");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("nested/bookmarks");
+
+    let mut store = BookmarkStore::default();
+    store.add(Bookmark::new(
+        Path::new("sgf/pro_game.sgf"),
+        78,
+        Some("The wedge"),
+    ));
+    store.add(Bookmark::new(Path::new("sgf/shodan_game.sgf"), 12, None));
+    store.save(&path).unwrap();
+
+    let loaded = BookmarkStore::load(&path).unwrap();
+    assert_eq!(loaded, store);
+    // Paths are stored absolute
+    let absolute = std::env::current_dir().unwrap().join("sgf/pro_game.sgf");
+    assert_eq!(loaded.bookmarks()[0].path, absolute);
+    assert_eq!(loaded.find(&absolute, 78), Some(0));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_bookmark_same_position_updates_label() {
+    let mut store = BookmarkStore::default();
+    store.add(Bookmark::new(Path::new("game.sgf"), 5, Some("first")));
+    store.add(Bookmark::new(Path::new("game.sgf"), 6, None));
+    store.add(Bookmark::new(Path::new("game.sgf"), 5, Some("second")));
+    assert_eq!(store.bookmarks().len(), 2);
+    assert_eq!(store.bookmarks()[0].label.as_deref(), Some("second"));
+
+    let removed = store.remove(Path::new("game.sgf"), 5).unwrap();
+    assert_eq!(removed.move_number, 5);
+    assert_eq!(store.remove(Path::new("game.sgf"), 5), None);
+    assert_eq!(store.bookmarks().len(), 1);
+}
+
+#[test]
+fn test_bookmark_label_whitespace() {
+    let bookmark = Bookmark::new(Path::new("game.sgf"), 1, Some("  a\tlong\n label "));
+    assert_eq!(bookmark.label.as_deref(), Some("a long label"));
+    let bookmark = Bookmark::new(Path::new("game.sgf"), 1, Some("   "));
+    assert_eq!(bookmark.label, None);
+}
+
+#[test]
+fn test_load_missing_or_damaged_bookmarks() {
+    let missing = std::env::temp_dir().join("smartgameviewer_no_such_bookmarks");
+    assert_eq!(
+        BookmarkStore::load(&missing).unwrap(),
+        BookmarkStore::default()
+    );
+
+    let path = std::env::temp_dir().join("smartgameviewer_damaged_bookmarks");
+    fs::write(
+        &path,
+        "# smartgameviewer bookmarks\n/games/a.sgf\tlots\tBad\n/games/b.sgf\t12\n\ngarbage\n",
+    )
+    .unwrap();
+    let store = BookmarkStore::load(&path).unwrap();
+    assert_eq!(store.bookmarks().len(), 1);
+    assert_eq!(store.find(Path::new("/games/b.sgf"), 12), Some(0));
+    assert_eq!(store.bookmarks()[0].label, None);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_playlist_find_or_add() {
+    let mut playlist = PlaylistManager::new(Some("sgf/pro_game.sgf")).unwrap();
+    let absolute = std::env::current_dir().unwrap().join("sgf/pro_game.sgf");
+    assert_eq!(playlist.find_or_add(&absolute), 0);
+    assert_eq!(playlist.find_or_add(Path::new("sgf/shodan_game.sgf")), 1);
+    assert_eq!(playlist.files().len(), 2);
+}
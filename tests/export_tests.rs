@@ -0,0 +1,386 @@
+use smartgameviewer::{
+    export::animation::{render_animation, render_frames, AnimationFormat, AnimationOptions},
+    export::kifu::{kifu_diagrams, render_kifu_html, KifuOptions},
+    export::text::{render_text, TextStyle},
+    export::{self, png::render_png, raster::render_image, svg::render_svg, DiagramOptions, Theme},
+    game::GameState,
+    parser::{parse_sgf, Color, Move},
+};
+use std::path::Path;
+
+fn sample_game() -> GameState {
+    let moves = vec![
+        Move {
+            color: Color::Black,
+            position: Some((3, 3)),
+            ..Default::default()
+        },
+        Move {
+            color: Color::White,
+            position: Some((15, 15)),
+            ..Default::default()
+        },
+    ];
+    let mut game = GameState::new(19, moves);
+    game.jump_to_end();
+    game
+}
+
+#[test]
+fn test_column_and_row_labels() {
+    assert_eq!(export::column_label(0), 'A');
+    assert_eq!(export::column_label(7), 'H');
+    // 'I' is skipped
+    assert_eq!(export::column_label(8), 'J');
+    assert_eq!(export::column_label(18), 'T');
+
+    assert_eq!(export::row_label(0, 19), "19");
+    assert_eq!(export::row_label(18, 19), "1");
+}
+
+#[test]
+fn test_load_position() {
+    let game =
+        export::load_position(Path::new("examples/AlphaGo_LeeSedol_game4.sgf"), Some(10)).unwrap();
+    assert_eq!(game.current_move, 10);
+
+    let game =
+        export::load_position(Path::new("examples/AlphaGo_LeeSedol_game4.sgf"), None).unwrap();
+    assert_eq!(game.current_move, game.moves.len());
+
+    assert!(export::load_position(Path::new("/tmp/does_not_exist_xyz.sgf"), None).is_err());
+}
+
+#[test]
+fn test_svg_contains_board_elements() {
+    let svg = render_svg(&sample_game(), &DiagramOptions::default());
+
+    assert!(svg.starts_with("<?xml"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    // 19 horizontal + 19 vertical grid lines
+    assert_eq!(svg.matches("<line").count(), 38);
+    // Two stones, last-move marker, and the 7 star points not covered by stones
+    assert_eq!(svg.matches("fill=\"#000000\" stroke").count(), 1);
+    assert_eq!(svg.matches("fill=\"#ffffff\" stroke").count(), 1);
+    assert_eq!(svg.matches("fill=\"none\"").count(), 1);
+    assert_eq!(svg.matches("r=\"3\"").count(), 7);
+    // Coordinates on four sides, skipping I
+    assert_eq!(svg.matches(">T</text>").count(), 2);
+    assert!(!svg.contains(">I</text>"));
+}
+
+#[test]
+fn test_svg_options() {
+    let options = DiagramOptions {
+        coordinates: false,
+        move_numbers: true,
+        ..DiagramOptions::default()
+    };
+    let svg = render_svg(&sample_game(), &options);
+
+    assert!(!svg.contains(">A</text>"));
+    assert!(svg.contains(">1</text>"));
+    // Last move number is highlighted instead of a marker
+    assert!(svg.contains(
+        "fill=\"#dd0000\" text-anchor=\"middle\" dominant-baseline=\"central\">2</text>"
+    ));
+    assert!(!svg.contains("fill=\"none\""));
+}
+
+#[test]
+fn test_svg_rotation() {
+    let game = sample_game();
+    let options = DiagramOptions {
+        rotation: 2,
+        ..DiagramOptions::default()
+    };
+    let svg = render_svg(&game, &options);
+
+    // Black stone at board (3, 3) appears at view (15, 15) after 180°
+    // margin 36 + 15 * 24 = 396
+    assert!(svg.contains("<circle cx=\"396\" cy=\"396\" r=\"11.28\" fill=\"#000000\""));
+}
+
+#[test]
+fn test_svg_theme() {
+    let options = DiagramOptions {
+        theme: Theme::Dark,
+        ..DiagramOptions::default()
+    };
+    let svg = render_svg(&sample_game(), &options);
+
+    assert!(svg.contains("fill=\"#2b2b2b\""));
+    assert!(!svg.contains("#d2b48c"));
+}
+
+#[test]
+fn test_theme_names() {
+    assert_eq!(Theme::from_name("wood"), Some(Theme::Wood));
+    assert_eq!(Theme::from_name("Dark"), Some(Theme::Dark));
+    assert_eq!(Theme::from_name("neon"), None);
+    assert_eq!(Theme::Light.name(), "light");
+}
+
+#[test]
+fn test_render_image_matches_board_view() {
+    // End on a pass so no last-move marker covers the stones
+    let mut game = sample_game();
+    game.moves.push(Move {
+        color: Color::Black,
+        position: None,
+        ..Default::default()
+    });
+    game.jump_to_end();
+    let palette = Theme::Wood.palette();
+
+    // 400px with coordinates: 18 cells + 3 margin cells, so the cell is 400/21
+    let cell = 400.0 / 21.0;
+    let center = |index: u8| (cell * 1.5 + cell * index as f32) as u32;
+
+    let image = render_image(&game, &DiagramOptions::default(), 400);
+    assert_eq!((image.width, image.height), (400, 400));
+    assert_eq!(image.pixels.len(), 400 * 400 * 3);
+    assert_eq!(image.pixel(center(3), center(3)), palette.black_stone);
+    assert_eq!(image.pixel(center(15), center(15)), palette.white_stone);
+    // Empty board away from lines
+    assert_eq!(image.pixel(center(9) + 5, center(9) + 5), palette.board);
+
+    // 180° rotation uses the same mapping as BoardView
+    let options = DiagramOptions {
+        rotation: 2,
+        ..DiagramOptions::default()
+    };
+    let image = render_image(&game, &options, 400);
+    assert_eq!(image.pixel(center(15), center(15)), palette.black_stone);
+    assert_eq!(image.pixel(center(3), center(3)), palette.white_stone);
+}
+
+#[test]
+fn test_render_png() {
+    let png = render_png(&sample_game(), &DiagramOptions::default(), 200).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+}
+
+#[test]
+fn test_render_frames_covers_move_range() {
+    let mut game = sample_game();
+    let options = AnimationOptions {
+        pixel_size: 100,
+        ..AnimationOptions::default()
+    };
+
+    // Empty board plus one frame per move
+    let frames = render_frames(&mut game, &options);
+    assert_eq!(frames.len(), 3);
+    assert_ne!(frames[0], frames[1]);
+    assert_ne!(frames[1], frames[2]);
+
+    let options = AnimationOptions {
+        pixel_size: 100,
+        first_move: 1,
+        last_move: Some(1),
+        ..AnimationOptions::default()
+    };
+    assert_eq!(render_frames(&mut game, &options).len(), 1);
+    assert_eq!(game.current_move, 1);
+
+    // Out of range values are clamped to the game
+    let options = AnimationOptions {
+        pixel_size: 100,
+        first_move: 5,
+        last_move: Some(99),
+        ..AnimationOptions::default()
+    };
+    assert_eq!(render_frames(&mut game, &options).len(), 1);
+}
+
+#[test]
+fn test_render_gif() {
+    let mut game = sample_game();
+    let options = AnimationOptions {
+        pixel_size: 100,
+        frame_delay_ms: 250,
+        ..AnimationOptions::default()
+    };
+    let data = render_animation(&mut game, &options, AnimationFormat::Gif).unwrap();
+    assert_eq!(&data[..6], b"GIF89a");
+
+    let mut decoder = gif::DecodeOptions::new()
+        .read_info(data.as_slice())
+        .unwrap();
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    assert_eq!(delays, vec![25, 25, 25]);
+}
+
+#[test]
+fn test_render_apng() {
+    let mut game = sample_game();
+    let options = AnimationOptions {
+        pixel_size: 100,
+        ..AnimationOptions::default()
+    };
+    let data = render_animation(&mut game, &options, AnimationFormat::Apng).unwrap();
+
+    let decoder = png::Decoder::new(data.as_slice());
+    let reader = decoder.read_info().unwrap();
+    let animation = reader.info().animation_control().unwrap();
+    assert_eq!(animation.num_frames, 3);
+    assert_eq!(animation.num_plays, 0); // Loops forever
+}
+
+#[test]
+fn test_animation_format_names() {
+    assert_eq!(
+        AnimationFormat::from_name("GIF"),
+        Some(AnimationFormat::Gif)
+    );
+    assert_eq!(
+        AnimationFormat::from_name("apng"),
+        Some(AnimationFormat::Apng)
+    );
+    assert_eq!(
+        AnimationFormat::from_name("png"),
+        Some(AnimationFormat::Apng)
+    );
+    assert_eq!(AnimationFormat::from_name("mp4"), None);
+}
+
+#[test]
+fn test_render_text_ascii() {
+    let game = sample_game();
+    let text = render_text(&game, &DiagramOptions::default(), TextStyle::Ascii);
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines[0], "Black vs White");
+    assert_eq!(lines[1].trim(), "A B C D E F G H J K L M N O P Q R S T");
+    assert_eq!(lines.len(), 19 + 3);
+    assert!(text.is_ascii());
+
+    // Board row 3 is line "16", board row 15 is line "4"
+    let row16 = lines[2 + 3];
+    assert!(row16.starts_with("16 +-+-+-X-+"));
+    assert!(row16.ends_with(" 16"));
+    assert!(lines[2 + 15].contains("-O-+-+-+ 4"));
+    assert_eq!(lines[lines.len() - 1], "Move 2/2 | White Q4");
+}
+
+#[test]
+fn test_render_text_options() {
+    let game = sample_game();
+    let options = DiagramOptions {
+        coordinates: false,
+        rotation: 2,
+        ..Default::default()
+    };
+    let text = render_text(&game, &options, TextStyle::Ascii);
+    let lines: Vec<&str> = text.lines().collect();
+
+    // No label rows, and 180° puts the black stone at view (15, 15)
+    assert_eq!(lines.len(), 19 + 2);
+    assert!(lines[1 + 15].starts_with("+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-X-+-+-+"));
+    assert_eq!(lines[1 + 3], "+-+-+-O-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+");
+    assert_eq!(lines[lines.len() - 1], "Move 2/2 | White D16");
+}
+
+#[test]
+fn test_render_text_ansi() {
+    let game = sample_game();
+    let text = render_text(&game, &DiagramOptions::default(), TextStyle::Ansi);
+
+    assert!(text.contains("\x1b[48;2;210;180;140m"));
+    assert!(text.contains("\x1b[0m"));
+    assert!(text.contains("⚫"));
+    assert!(text.contains("⚪"));
+    assert!(text.contains("┌"));
+    assert!(text.contains("┼"));
+}
+
+#[test]
+fn test_kifu_diagrams() {
+    let at = |row, col, comment: Option<&str>| Move {
+        color: Color::Black,
+        position: Some((row, col)),
+        comment: comment.map(str::to_string),
+        ..Default::default()
+    };
+    let moves = vec![
+        at(3, 3, Some("Opening")),
+        at(15, 15, None),
+        at(3, 3, None),
+        Move {
+            color: Color::White,
+            position: None,
+            ..Default::default()
+        },
+        at(10, 10, Some("  ")),
+    ];
+    let mut game = GameState::new(19, moves);
+    let options = KifuOptions {
+        diagram: DiagramOptions {
+            coordinates: false,
+            ..Default::default()
+        },
+        moves_per_diagram: 4,
+    };
+    let diagrams = kifu_diagrams(&mut game, &options);
+
+    assert_eq!(diagrams.len(), 2);
+    assert_eq!((diagrams[0].first_move, diagrams[0].last_move), (1, 4));
+    assert_eq!((diagrams[1].first_move, diagrams[1].last_move), (5, 5));
+    assert_eq!(diagrams[0].notes, vec!["3 at 1", "4: White passes"]);
+    assert_eq!(diagrams[0].comments, vec![(1, "Opening".to_string())]);
+    assert!(diagrams[1].notes.is_empty());
+    assert!(diagrams[1].comments.is_empty());
+
+    // New moves are numbered; stones from earlier diagrams are not
+    assert!(diagrams[0].svg.contains(">1</text>"));
+    assert!(diagrams[0].svg.contains(">2</text>"));
+    assert!(!diagrams[0].svg.contains(">3</text>"));
+    assert!(diagrams[1].svg.contains(">5</text>"));
+    assert!(!diagrams[1].svg.contains(">1</text>"));
+    // Three stones, two of them covering star points
+    assert_eq!(diagrams[1].svg.matches("<circle").count(), 3 + 7);
+}
+
+#[test]
+fn test_kifu_comments_from_sgf() {
+    let sgf = "(;GM[1]SZ[19]C[Club game];B[dd]C[Star point];W[pp];C[Time out]B[dp];W[pd]C[Tenuki])";
+    let tree = parse_sgf(sgf).unwrap();
+    let mut game = GameState::with_properties(19, tree.moves, tree.properties);
+    let options = KifuOptions {
+        moves_per_diagram: 2,
+        ..Default::default()
+    };
+
+    let diagrams = kifu_diagrams(&mut game, &options);
+    assert_eq!(diagrams[0].comments, vec![(1, "Star point".to_string())]);
+    assert_eq!(
+        diagrams[1].comments,
+        vec![(3, "Time out".to_string()), (4, "Tenuki".to_string())]
+    );
+
+    let html = render_kifu_html(&mut game, &options);
+    assert!(html.contains("<p class=\"comment\">Club game</p>"));
+    assert!(html.contains("<li><b>1</b> Star point</li>"));
+    assert!(html.contains("<li><b>4</b> Tenuki</li>"));
+}
+
+#[test]
+fn test_render_kifu_html() {
+    let mut game = sample_game();
+    game.properties
+        .insert("GN".to_string(), vec!["Club <Final>".to_string()]);
+    game.properties
+        .insert("PB".to_string(), vec!["Lee & Kim".to_string()]);
+    let html = render_kifu_html(&mut game, &KifuOptions::default());
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Club &lt;Final&gt;</title>"));
+    assert!(html.contains("<td>Lee &amp; Kim</td>"));
+    assert_eq!(html.matches("<svg").count(), 1);
+    assert!(html.contains("Moves 1–2"));
+    assert!(!html.contains("<?xml"));
+}
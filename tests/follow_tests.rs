@@ -0,0 +1,34 @@
+use smartgameviewer::follow::{read_growing_game, FileFollower};
+use std::fs;
+
+#[test]
+fn test_follower_reports_changes() {
+    let path = std::env::temp_dir().join("smartgameviewer_follow_test.sgf");
+    fs::write(&path, "(;GM[1];B[dd])").unwrap();
+
+    let mut follower = FileFollower::new(&path);
+    assert_eq!(follower.poll().unwrap().moves.len(), 1);
+    assert!(follower.poll().is_none());
+
+    fs::write(&path, "(;GM[1];B[dd];W[pp])").unwrap();
+    assert_eq!(follower.poll().unwrap().moves.len(), 2);
+    assert!(follower.poll().is_none());
+
+    fs::remove_file(&path).unwrap();
+    assert!(follower.poll().is_none());
+}
+
+#[test]
+fn test_read_growing_game_without_closing_paren() {
+    let path = std::env::temp_dir().join("smartgameviewer_growing_test.sgf");
+    fs::write(&path, "(;GM[1]PB[Lee];B[dd];W[pp]\n").unwrap();
+    let tree = read_growing_game(&path).unwrap();
+    assert_eq!(tree.moves.len(), 2);
+
+    fs::write(&path, "(;GM[1];B[dd];W[pp]").unwrap();
+    assert_eq!(read_growing_game(&path).unwrap().moves.len(), 2);
+
+    fs::write(&path, "not a game").unwrap();
+    assert!(read_growing_game(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
@@ -15,17 +15,17 @@ fn test_game_state_navigation() {
         Move {
             color: Color::Black,
             position: Some((3, 3)), // row 3, col 3
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((3, 15)), // row 3, col 15
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::Black,
             position: Some((15, 3)), // row 15, col 3
-            comment: None,
+            ..Default::default()
         },
     ];
 
@@ -81,17 +81,18 @@ fn test_coordinate_system_correctness() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_game_looping_enabled() {
     let moves = vec![
         Move {
             color: Color::Black,
             position: Some((3, 3)),
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((3, 15)),
-            comment: None,
+            ..Default::default()
         },
     ];
 
@@ -115,17 +116,18 @@ fn test_game_looping_enabled() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_game_looping_disabled() {
     let moves = vec![
         Move {
             color: Color::Black,
             position: Some((3, 3)),
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((3, 15)),
-            comment: None,
+            ..Default::default()
         },
     ];
 
@@ -146,11 +148,12 @@ fn test_game_looping_disabled() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_toggle_looping() {
     let moves = vec![Move {
         color: Color::Black,
         position: Some((3, 3)),
-        comment: None,
+        ..Default::default()
     }];
 
     let mut game = GameState::new(19, moves);
@@ -173,12 +176,12 @@ fn test_board_rotates_on_loop() {
         Move {
             color: Color::Black,
             position: Some((3, 3)),
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((3, 4)),
-            comment: None,
+            ..Default::default()
         },
     ];
     let mut game = GameState::new(19, moves);
@@ -199,7 +202,7 @@ fn test_multiple_loop_rotations() {
     let moves = vec![Move {
         color: Color::Black,
         position: Some((3, 3)),
-        comment: None,
+        ..Default::default()
     }];
     let mut game = GameState::new(19, moves);
 
@@ -222,11 +225,12 @@ fn test_multiple_loop_rotations() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_no_rotation_when_looping_disabled() {
     let moves = vec![Move {
         color: Color::Black,
         position: Some((3, 3)),
-        comment: None,
+        ..Default::default()
     }];
     let mut game = GameState::new(19, moves);
     game.set_looping(false);
@@ -256,11 +260,12 @@ fn test_with_properties_and_get_property() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_previous_at_move_zero() {
     let moves = vec![Move {
         color: Color::Black,
         position: Some((3, 3)),
-        comment: None,
+        ..Default::default()
     }];
     let mut game = GameState::new(19, moves);
 
@@ -277,12 +282,12 @@ fn test_next_with_pass_move() {
         Move {
             color: Color::Black,
             position: None, // pass
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((3, 3)),
-            comment: None,
+            ..Default::default()
         },
     ];
     let mut game = GameState::new(19, moves);
@@ -319,7 +324,7 @@ fn test_jump_to_start_when_already_at_start() {
     let moves = vec![Move {
         color: Color::Black,
         position: Some((3, 3)),
-        comment: None,
+        ..Default::default()
     }];
     let mut game = GameState::new(19, moves);
 
@@ -345,12 +350,12 @@ fn test_non_19x19_board() {
         Move {
             color: Color::Black,
             position: Some((0, 0)),
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((8, 8)),
-            comment: None,
+            ..Default::default()
         },
     ];
     let mut game = GameState::new(9, moves);
@@ -367,3 +372,224 @@ fn test_non_19x19_board() {
     assert_eq!(game.board.get(8, 8), None);
     assert_eq!(game.board.get(0, 0), Some(Color::Black));
 }
+
+#[test]
+fn test_jump_to_move() {
+    let moves = vec![
+        Move {
+            color: Color::Black,
+            position: Some((3, 3)),
+            ..Default::default()
+        },
+        Move {
+            color: Color::White,
+            position: Some((15, 15)),
+            ..Default::default()
+        },
+        Move {
+            color: Color::Black,
+            position: Some((3, 15)),
+            ..Default::default()
+        },
+    ];
+    let mut game = GameState::new(19, moves);
+
+    game.jump_to_move(2);
+    assert_eq!(game.current_move, 2);
+    assert_eq!(game.board.get(3, 3), Some(Color::Black));
+    assert_eq!(game.board.get(15, 15), Some(Color::White));
+    assert_eq!(game.board.get(3, 15), None);
+
+    // Jumping backward rebuilds the board
+    game.jump_to_move(1);
+    assert_eq!(game.current_move, 1);
+    assert_eq!(game.board.get(15, 15), None);
+
+    // Past the end clamps to the last move
+    game.jump_to_move(99);
+    assert_eq!(game.current_move, 3);
+    assert_eq!(game.board.get(3, 15), Some(Color::Black));
+}
+
+#[test]
+fn test_move_number_at() {
+    let moves = vec![
+        Move {
+            color: Color::Black,
+            position: Some((3, 3)),
+            ..Default::default()
+        },
+        Move {
+            color: Color::White,
+            position: None, // Pass
+            ..Default::default()
+        },
+        Move {
+            color: Color::Black,
+            position: Some((9, 9)),
+            ..Default::default()
+        },
+    ];
+    let mut game = GameState::new(19, moves);
+
+    // Nothing played yet
+    assert_eq!(game.move_number_at(3, 3), None);
+
+    game.jump_to_end();
+    assert_eq!(game.move_number_at(3, 3), Some(1));
+    assert_eq!(game.move_number_at(9, 9), Some(3));
+    assert_eq!(game.move_number_at(0, 0), None);
+
+    // Moves after the current position are not considered
+    game.jump_to_move(2);
+    assert_eq!(game.move_number_at(9, 9), None);
+}
+
+#[test]
+fn test_known_and_unknown_properties() {
+    let mut props = std::collections::HashMap::new();
+    props.insert("RE".to_string(), vec!["W+R".to_string()]);
+    props.insert("EV".to_string(), vec!["Meijin".to_string()]);
+    props.insert("KM".to_string(), vec!["6.5".to_string()]);
+    props.insert("TM".to_string(), vec!["5400".to_string()]);
+    props.insert("FF".to_string(), vec!["4".to_string()]);
+    props.insert("AP".to_string(), vec!["CGoban:3".to_string()]);
+
+    let game = GameState::with_properties(19, vec![], props);
+
+    // Known properties come back with human names in display order
+    assert_eq!(
+        game.known_properties(),
+        vec![
+            ("Event", "Meijin".to_string()),
+            ("Komi", "6.5".to_string()),
+            ("Time limit", "1h 30m".to_string()),
+            ("Result", "W+R".to_string()),
+        ]
+    );
+
+    // Everything else is raw, sorted by key
+    assert_eq!(
+        game.unknown_properties(),
+        vec![("AP", "CGoban:3".to_string()), ("FF", "4".to_string())]
+    );
+}
+
+#[test]
+fn test_star_points() {
+    let points = Board::new(19).star_points();
+    assert_eq!(points.len(), 9);
+    assert!(points.contains(&(3, 3)));
+    assert!(points.contains(&(9, 9)));
+    assert!(points.contains(&(3, 9)));
+    assert!(points.contains(&(15, 15)));
+
+    let points = Board::new(13).star_points();
+    assert_eq!(points.len(), 5);
+    assert!(points.contains(&(6, 6)));
+
+    let points = Board::new(9).star_points();
+    assert_eq!(points.len(), 5);
+    assert!(points.contains(&(2, 2)));
+    assert!(points.contains(&(4, 4)));
+
+    assert!(Board::new(5).star_points().is_empty());
+}
+
+#[test]
+fn test_update_from_tree_follows_the_end() {
+    let short = "(;GM[1]SZ[9];B[aa];W[bb])";
+    let longer = "(;GM[1]SZ[9];B[aa];W[bb];B[cc];W[dd])";
+
+    // At the end: stays at the latest move
+    let mut game = GameState::from_tree(parse_sgf(short).unwrap());
+    game.jump_to_end();
+    game.update_from_tree(parse_sgf(longer).unwrap());
+    assert_eq!(game.current_move, 4);
+    assert_eq!(game.board.get(3, 3), Some(Color::White));
+
+    // Reviewing an earlier move: stays there
+    let mut game = GameState::from_tree(parse_sgf(short).unwrap());
+    game.jump_to_move(1);
+    game.update_from_tree(parse_sgf(longer).unwrap());
+    assert_eq!(game.current_move, 1);
+    assert_eq!(game.moves.len(), 4);
+    assert_eq!(game.board.get(1, 1), None);
+}
+
+#[test]
+fn test_setup_stones_placed_before_first_move() {
+    let tree = parse_sgf("(;SZ[9]HA[2]AB[cc][gg]AW[ac:bd];W[ee])").unwrap();
+    let mut game = GameState::with_properties(9, tree.moves, tree.properties);
+    assert_eq!(game.board.get(2, 2), Some(Color::Black));
+    assert_eq!(game.board.get(6, 6), Some(Color::Black));
+    // "ac:bd" is the rectangle A7:B6
+    for point in [(2, 0), (2, 1), (3, 0), (3, 1)] {
+        assert_eq!(game.board.get(point.0, point.1), Some(Color::White));
+    }
+
+    game.jump_to_end();
+    assert_eq!(game.board.get(4, 4), Some(Color::White));
+    // Going back to the start keeps them
+    game.jump_to_start();
+    assert_eq!(game.board.get(2, 2), Some(Color::Black));
+    assert_eq!(game.board.get(4, 4), None);
+    game.next();
+    game.previous();
+    assert_eq!(game.board.get(6, 6), Some(Color::Black));
+}
+
+#[test]
+fn test_play_captures_stones() {
+    let mut board = Board::new(9);
+    board.set(0, 0, Color::White);
+    board.set(0, 1, Color::White);
+    board.set(1, 0, Color::Black);
+    board.set(1, 1, Color::Black);
+    // Filling the last liberty takes the whole group
+    assert_eq!(board.play(0, 2, Color::Black), vec![(0, 1), (0, 0)]);
+    assert_eq!(board.get(0, 0), None);
+    assert_eq!(board.get(0, 1), None);
+
+    assert_eq!(board.play(5, 5, Color::White), Vec::<(u8, u8)>::new());
+
+    // A stone without liberties that captures nothing is removed (suicide)
+    let mut board = Board::new(9);
+    board.set(0, 1, Color::Black);
+    board.set(1, 0, Color::Black);
+    assert_eq!(board.play(0, 0, Color::White), vec![(0, 0)]);
+    assert_eq!(board.get(0, 0), None);
+}
+
+#[test]
+fn test_replay_removes_captured_stones() {
+    let tree = parse_sgf("(;SZ[9];B[ba];W[aa];B[ab];W[ee])").unwrap();
+    let mut game = GameState::from_tree(tree);
+    game.jump_to_end();
+    assert_eq!(game.board.get(0, 0), None);
+
+    // Stepping and jumping agree
+    game.jump_to_start();
+    for _ in 0..3 {
+        game.next();
+    }
+    assert_eq!(game.board.get(0, 0), None);
+    assert_eq!(game.move_number_at(0, 0), None);
+    game.jump_to_move(2);
+    assert_eq!(game.board.get(0, 0), Some(Color::White));
+    assert_eq!(game.move_number_at(0, 0), Some(2));
+}
+
+#[test]
+fn test_invalid_board_size_falls_back_to_19() {
+    let mut game = GameState::from_tree(parse_sgf("(;SZ[0];B[aa];W[ss])").unwrap());
+    assert_eq!(game.board.size, 19);
+    game.jump_to_end();
+    assert_eq!(game.board.get(18, 18), Some(Color::White));
+
+    // Loading the file reports it instead
+    let path = std::env::temp_dir().join("smartgameviewer_sz0_test.sgf");
+    std::fs::write(&path, "(;SZ[0];B[aa])").unwrap();
+    assert!(load_game_from_path(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,188 @@
+use smartgameviewer::game::{load_game_from_path, GameState};
+use smartgameviewer::import::{
+    gib::parse_gib, is_game_file, ngf::parse_ngf, ugf::parse_ugf, GameFormat,
+};
+use smartgameviewer::parser::Color;
+use smartgameviewer::playlist::PlaylistManager;
+use std::fs;
+use std::path::Path;
+
+const GIB: &str = "\\HS
+\\[GAMENAME=Tygem Open\\]
+\\[GAMEDATE=2016- 3-13-19-04-04\\]
+\\[GAMEBLACKNAME=Lee Sedol (9D)\\]
+\\[GAMEWHITENAME=Park Junghwan (9D)\\]
+\\[GAMERESULT=White wins\\]
+\\[GAMEINFOMAIN=GBKIND:3,GTYPE:0,GRLT:4,ZIPSU:0,DUM:6500\\]
+\\HE
+\\GS
+2 1 0
+119 0 &4
+INI 0 1 0 &4
+STO 0 2 1 15 3
+STO 0 3 2 3 15
+SKI 0 4
+\\GE
+";
+
+const NGF: &str = "Meijin League
+19
+Cho Chikun      9D*
+Iyama Yuta      9D*
+www.cyberoro.com
+0
+0
+6
+20160313 [13:00]
+5
+White wins by resignation!
+3
+PMAABQEQE
+PMABWDQDQ
+PMACBAAAA
+";
+
+const UGF: &str = "[Header]
+Lang=JP
+Date=2011/03/26,14:00
+Hdcp=0,6.5
+Size=19
+Winner=B,3.5
+PlayerB=Honinbo,9p,,
+PlayerW=Kisei,9p,,
+Title=Study Game
+Place=Pandanet
+[Data]
+QP,B1,1,0
+DD,W2,2,0
+YA,B3,3,0
+";
+
+#[test]
+fn test_parse_gib() {
+    let tree = parse_gib(GIB).unwrap();
+    let get = |key: &str| tree.properties.get(key).map(|v| v[0].as_str());
+
+    assert_eq!(get("PB"), Some("Lee Sedol"));
+    assert_eq!(get("BR"), Some("9D"));
+    assert_eq!(get("PW"), Some("Park Junghwan"));
+    assert_eq!(get("GN"), Some("Tygem Open"));
+    assert_eq!(get("DT"), Some("2016-03-13"));
+    assert_eq!(get("RE"), Some("W+R"));
+    assert_eq!(get("KM"), Some("6.5"));
+    assert_eq!(get("SZ"), Some("19"));
+
+    assert_eq!(tree.moves.len(), 3);
+    assert_eq!(tree.moves[0].color, Color::Black);
+    assert_eq!(tree.moves[0].position, Some((3, 15)));
+    assert_eq!(tree.moves[1].color, Color::White);
+    assert_eq!(tree.moves[1].position, Some((15, 3)));
+    assert_eq!(tree.moves[2].color, Color::Black);
+    assert_eq!(tree.moves[2].position, None);
+
+    assert!(parse_gib("not a gib file").is_err());
+}
+
+#[test]
+fn test_parse_gib_handicap() {
+    let gib = GIB.replace("INI 0 1 0 &4", "INI 0 1 3 &4");
+    let tree = parse_gib(&gib).unwrap();
+    assert_eq!(tree.properties["HA"], vec!["3"]);
+    assert_eq!(tree.properties["AB"], vec!["pd", "dp", "pp"]);
+
+    // The handicap stones are on the board before the first move
+    let game = GameState::with_properties(19, tree.moves, tree.properties);
+    assert_eq!(game.board.get(3, 15), Some(Color::Black));
+    assert_eq!(game.board.get(15, 3), Some(Color::Black));
+}
+
+#[test]
+fn test_parse_ngf() {
+    let tree = parse_ngf(NGF).unwrap();
+    let get = |key: &str| tree.properties.get(key).map(|v| v[0].as_str());
+
+    assert_eq!(get("GN"), Some("Meijin League"));
+    assert_eq!(get("PW"), Some("Cho Chikun"));
+    assert_eq!(get("WR"), Some("9D"));
+    assert_eq!(get("PB"), Some("Iyama Yuta"));
+    assert_eq!(get("KM"), Some("6.5"));
+    assert_eq!(get("DT"), Some("2016-03-13"));
+    assert_eq!(get("RE"), Some("W+R"));
+    assert_eq!(get("HA"), None);
+
+    assert_eq!(tree.moves.len(), 3);
+    assert_eq!(tree.moves[0].color, Color::Black);
+    assert_eq!(tree.moves[0].position, Some((3, 15)));
+    assert_eq!(tree.moves[1].color, Color::White);
+    assert_eq!(tree.moves[1].position, Some((15, 2)));
+    assert_eq!(tree.moves[2].position, None);
+
+    assert!(parse_ngf("too\nshort").is_err());
+}
+
+#[test]
+fn test_parse_ugf() {
+    let tree = parse_ugf(UGF).unwrap();
+    let get = |key: &str| tree.properties.get(key).map(|v| v[0].as_str());
+
+    assert_eq!(get("PB"), Some("Honinbo"));
+    assert_eq!(get("BR"), Some("9p"));
+    assert_eq!(get("PW"), Some("Kisei"));
+    assert_eq!(get("GN"), Some("Study Game"));
+    assert_eq!(get("DT"), Some("2011-03-26"));
+    assert_eq!(get("KM"), Some("6.5"));
+    assert_eq!(get("RE"), Some("B+3.5"));
+
+    // Rows count up from the bottom edge
+    assert_eq!(tree.moves.len(), 3);
+    assert_eq!(tree.moves[0].position, Some((3, 16)));
+    assert_eq!(tree.moves[1].color, Color::White);
+    assert_eq!(tree.moves[1].position, Some((15, 3)));
+    assert_eq!(tree.moves[2].position, None);
+
+    assert!(parse_ugf("QP,B1,1,0").is_err());
+}
+
+#[test]
+fn test_game_format_from_path() {
+    assert_eq!(GameFormat::from_path(Path::new("a.GIB")), GameFormat::Gib);
+    assert_eq!(GameFormat::from_path(Path::new("a.ngf")), GameFormat::Ngf);
+    assert_eq!(GameFormat::from_path(Path::new("a.ugi")), GameFormat::Ugf);
+    assert_eq!(GameFormat::from_path(Path::new("a.sgf")), GameFormat::Sgf);
+    assert_eq!(GameFormat::from_path(Path::new("noext")), GameFormat::Sgf);
+
+    assert!(is_game_file(Path::new("games/a.Sgf")));
+    assert!(is_game_file(Path::new("games/a.ugf")));
+    assert!(!is_game_file(Path::new("games/notes.txt")));
+    assert!(!is_game_file(Path::new("games/README")));
+}
+
+#[test]
+fn test_load_legacy_encoded_files() {
+    let dir = std::env::temp_dir().join("smartgameviewer_import_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    // Korean names saved as EUC-KR, as Tygem clients do
+    let gib = GIB.replace("Lee Sedol", "이세돌");
+    let (bytes, _, _) = encoding_rs::EUC_KR.encode(&gib);
+    fs::write(dir.join("b.gib"), &bytes).unwrap();
+    fs::write(dir.join("a.ngf"), NGF).unwrap();
+    fs::write(dir.join("c.ugf"), UGF).unwrap();
+    fs::write(dir.join("notes.txt"), "not a game").unwrap();
+
+    let game = load_game_from_path(&dir.join("b.gib")).unwrap();
+    assert_eq!(game.get_property("PB"), Some("이세돌"));
+    assert_eq!(game.moves.len(), 3);
+
+    // The playlist picks up every supported format and nothing else
+    let playlist = PlaylistManager::new(dir.to_str()).unwrap();
+    let names: Vec<String> = playlist
+        .files()
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["a.ngf", "b.gib", "c.ugf"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
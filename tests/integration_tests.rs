@@ -2,6 +2,7 @@ use smartgameviewer::{game::GameState, parser::parse_sgf};
 use std::fs;
 
 #[test]
+#[allow(clippy::len_zero)]
 fn test_load_alphago_game4() {
     let sgf_content = fs::read_to_string("examples/AlphaGo_LeeSedol_game4.sgf")
         .expect("Failed to read example SGF file");
@@ -48,6 +49,7 @@ fn test_load_alphago_game4() {
 }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn test_load_alphago_game5() {
     let sgf_content = fs::read_to_string("examples/AlphaGo_LeeSedol_game5.sgf")
         .expect("Failed to read example SGF file");
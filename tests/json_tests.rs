@@ -0,0 +1,119 @@
+#![cfg(feature = "serde")]
+
+use smartgameviewer::game::{load_game_from_path, GameState};
+use smartgameviewer::import::is_game_file;
+use smartgameviewer::json::{from_json, to_json};
+use smartgameviewer::parser::{parse_sgf, Color};
+use smartgameviewer::playlist::PlaylistManager;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_json_round_trip() {
+    let sgf =
+        "(;GM[1]SZ[19]PB[Black]PW[White]AB[aa][bb];B[dd]C[Opening]N[Hoshi]LB[dd:A];W[];B[pq])";
+    let tree = parse_sgf(sgf).unwrap();
+    let json = to_json(&tree);
+    let parsed = from_json(&json).unwrap();
+
+    assert_eq!(parsed.properties, tree.properties);
+    assert_eq!(parsed.moves.len(), 3);
+    assert_eq!(parsed.moves[0].color, Color::Black);
+    assert_eq!(parsed.moves[0].position, Some((3, 3)));
+    assert_eq!(parsed.moves[0].comment.as_deref(), Some("Opening"));
+    assert_eq!(parsed.moves[0].name.as_deref(), Some("Hoshi"));
+    assert_eq!(parsed.moves[0].labels, tree.moves[0].labels);
+    assert_eq!(parsed.moves[1].position, None);
+    assert_eq!(parsed.moves[2].position, Some((16, 15)));
+}
+
+#[test]
+fn test_json_layout() {
+    let tree = parse_sgf("(;SZ[9]PW[W]PB[B];B[cc];W[])").unwrap();
+    let json = to_json(&tree);
+
+    // Properties come out sorted so the output is stable
+    let keys: Vec<usize> = ["\"PB\"", "\"PW\"", "\"SZ\""]
+        .iter()
+        .map(|key| json.find(key).unwrap())
+        .collect();
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["moves"][0]["color"], "black");
+    assert_eq!(value["moves"][0]["position"], serde_json::json!([2, 2]));
+    assert!(value["moves"][1]["position"].is_null());
+    assert!(value["moves"][1].get("comment").is_none());
+    assert!(value["moves"][1].get("name").is_none());
+    assert!(value["moves"][1].get("labels").is_none());
+}
+
+#[test]
+fn test_json_setup_stones_and_variations() {
+    let sgf = "(;SZ[9]HA[2]AB[cc][gg]AW[ac:ad];W[ee](;B[ce])(;B[ec]C[Other side];W[ce]))";
+    let tree = parse_sgf(sgf).unwrap();
+    let json = to_json(&tree);
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value["properties"].get("AB").is_none());
+    assert_eq!(value["setup"]["black"], serde_json::json!([[2, 2], [6, 6]]));
+    assert_eq!(value["setup"]["white"], serde_json::json!([[2, 0], [3, 0]]));
+    let variation = &value["moves"][1]["variations"][0];
+    assert_eq!(variation[0]["position"], serde_json::json!([2, 4]));
+    assert_eq!(variation[0]["comment"], "Other side");
+    assert_eq!(variation[1]["color"], "white");
+    assert!(value["moves"][0].get("variations").is_none());
+
+    // Setup stones go back to AB/AW and are placed on the board
+    let parsed = from_json(&json).unwrap();
+    assert_eq!(parsed.properties["AB"], vec!["cc", "gg"]);
+    assert_eq!(parsed.properties["AW"], vec!["ac", "ad"]);
+    assert_eq!(parsed.moves[1].variations[0].len(), 2);
+    let game = GameState::with_properties(9, parsed.moves, parsed.properties);
+    assert_eq!(game.board.get(6, 6), Some(Color::Black));
+
+    let off_board = r#"{"properties": {"SZ": ["9"]}, "setup": {"white": [[0, 9]]}, "moves": []}"#;
+    assert!(from_json(off_board).is_err());
+    let variation_off_board = r#"{"properties": {"SZ": ["9"]}, "moves": [{"color": "black",
+        "position": [0, 0], "variations": [[{"color": "black", "position": [9, 9]}]]}]}"#;
+    assert!(from_json(variation_off_board).is_err());
+}
+
+#[test]
+fn test_from_json_errors() {
+    assert!(from_json("not json").is_err());
+    assert!(from_json(r#"{"properties": {}}"#).is_err());
+
+    // Minimal document without comments
+    let tree =
+        from_json(r#"{"properties": {}, "moves": [{"color": "white", "position": [18, 0]}]}"#)
+            .unwrap();
+    assert_eq!(tree.moves[0].color, Color::White);
+
+    // Off-board moves are rejected against SZ
+    let off_board =
+        r#"{"properties": {"SZ": ["9"]}, "moves": [{"color": "black", "position": [9, 0]}]}"#;
+    assert!(from_json(off_board).is_err());
+}
+
+#[test]
+fn test_load_json_file() {
+    let dir = std::env::temp_dir().join("sgv_json_test");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("game.JSON");
+    let tree = parse_sgf("(;SZ[13]GN[From JSON];B[dd];W[jj])").unwrap();
+    fs::write(&path, to_json(&tree)).unwrap();
+
+    let game = load_game_from_path(&path).unwrap();
+    assert_eq!(game.board.size, 13);
+    assert_eq!(game.get_property("GN"), Some("From JSON"));
+    assert_eq!(game.moves.len(), 2);
+
+    // JSON games are picked up in folder playlists
+    assert!(is_game_file(Path::new("game.json")));
+    let playlist = PlaylistManager::new(dir.to_str()).unwrap();
+    assert_eq!(playlist.files().len(), 1);
+    assert_eq!(playlist.files()[0], path);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
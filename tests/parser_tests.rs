@@ -71,6 +71,12 @@ fn test_parse_sgf_whitespace_handling() {
     assert_eq!(game.properties.get("GM").unwrap()[0], "1");
 }
 
+#[test]
+fn test_parse_sgf_whitespace_before_first_node() {
+    let game = parse_sgf("(\n  ;GM[1]PB[Zero])").unwrap();
+    assert_eq!(game.properties.get("PB").unwrap()[0], "Zero");
+}
+
 #[test]
 fn test_parse_multi_value_properties() {
     // AB[dd][ee] should produce a Vec with 2 entries
@@ -94,3 +100,115 @@ fn test_non_root_node_properties_ignored() {
     // C from non-root nodes should NOT be in properties
     assert!(!result.properties.contains_key("C"));
 }
+
+#[test]
+fn test_move_comments_attached() {
+    let sgf = "(;GM[1]C[game comment];B[dd]C[first];C[before]W[pp];B[qq])";
+    let result = parse_sgf(sgf).unwrap();
+    assert_eq!(result.moves[0].comment.as_deref(), Some("first"));
+    assert_eq!(result.moves[1].comment.as_deref(), Some("before"));
+    assert_eq!(result.moves[2].comment, None);
+    // The root comment stays a game property
+    assert_eq!(result.properties["C"], vec!["game comment".to_string()]);
+}
+
+#[test]
+fn test_comments_on_nodes_without_moves_kept() {
+    let sgf = "(;GM[1]C[game];AB[dd]C[handicap];B[pp];C[setup]AW[qq];W[dp]C[reply])";
+    let result = parse_sgf(sgf).unwrap();
+    assert_eq!(result.properties["C"], vec!["game\n\nhandicap".to_string()]);
+    assert_eq!(result.moves[0].comment.as_deref(), Some("setup"));
+    assert_eq!(result.moves[1].comment.as_deref(), Some("reply"));
+
+    let result = parse_sgf("(;GM[1];C[first node];B[dd])").unwrap();
+    assert_eq!(result.properties["C"], vec!["first node".to_string()]);
+}
+
+#[test]
+fn test_move_names_and_labels_attached() {
+    let sgf = "(;GM[1]N[Start];B[dd]N[Joseki]LB[pd:A][dp:tesuji];W[pp]LB[zz:bad][aa])";
+    let result = parse_sgf(sgf).unwrap();
+    assert_eq!(result.moves[0].name.as_deref(), Some("Joseki"));
+    assert_eq!(
+        result.moves[0].labels,
+        vec![
+            Label {
+                position: (3, 15),
+                text: "A".to_string()
+            },
+            Label {
+                position: (15, 3),
+                text: "tesuji".to_string()
+            },
+        ]
+    );
+    // Labels off the board or without a colon are dropped
+    assert!(result.moves[1].labels.is_empty());
+    assert_eq!(result.properties["N"], vec!["Start".to_string()]);
+}
+
+#[test]
+fn test_variations_kept_off_the_main_line() {
+    let sgf = "(;GM[1];B[dd](;W[pp];B[dp](;W[pd])(;W[qd]C[also good]))(;W[dp];B[pp]))";
+    let result = parse_sgf(sgf).unwrap();
+    let main: Vec<_> = result.moves.iter().map(|mv| mv.position).collect();
+    assert_eq!(
+        main,
+        vec![Some((3, 3)), Some((15, 15)), Some((15, 3)), Some((3, 15))]
+    );
+
+    // W[dp] is an alternative to move 2, W[qd] to move 4
+    assert_eq!(result.moves[1].variations.len(), 1);
+    let line: Vec<_> = result.moves[1].variations[0]
+        .iter()
+        .map(|mv| mv.position)
+        .collect();
+    assert_eq!(line, vec![Some((15, 3)), Some((15, 15))]);
+    let alternative = &result.moves[3].variations[0][0];
+    assert_eq!(alternative.position, Some((3, 16)));
+    assert_eq!(alternative.comment.as_deref(), Some("also good"));
+    assert!(result.moves[0].variations.is_empty());
+}
+
+#[test]
+fn test_property_value_escapes() {
+    let sgf = "(;GM[1]GN[a \\] b];B[dd]C[Not a node; still the comment])";
+    let result = parse_sgf(sgf).unwrap();
+    assert_eq!(result.properties["GN"], vec!["a ] b".to_string()]);
+    assert_eq!(result.moves.len(), 1);
+    assert_eq!(
+        result.moves[0].comment.as_deref(),
+        Some("Not a node; still the comment")
+    );
+}
+
+#[test]
+fn test_split_collection() {
+    let input = "(;GN[one (1)];B[dd](;W[pp])(;W[dp]))\n(;GN[two \\] )];B[aa])  junk";
+    let games = split_collection(input);
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0], "(;GN[one (1)];B[dd](;W[pp])(;W[dp]))");
+    assert_eq!(games[1], "(;GN[two \\] )];B[aa])");
+
+    assert!(split_collection("no games here").is_empty());
+}
+
+#[test]
+fn test_board_checked_against_size() {
+    let check = |sgf: &str| parse_sgf(sgf).unwrap().check_board();
+    assert!(check("(;SZ[9];B[ii];W[aa])").is_ok());
+    assert!(check("(;B[ss])").is_ok());
+    assert_eq!(parse_sgf("(;SZ[13])").unwrap().board_size().unwrap(), 13);
+
+    // Moves, variations and setup stones off a 9x9 board
+    assert!(check("(;SZ[9];B[aa];W[ss])").is_err());
+    assert!(check("(;SZ[9];B[aa](;W[bb])(;W[jj]))").is_err());
+    assert!(check("(;SZ[9]AB[aa:jj];B[cc])").is_err());
+
+    // Sizes the coordinates can't reach
+    for size in ["0", "20", "255", "19:19", "nine"] {
+        let tree = parse_sgf(&format!("(;SZ[{}];B[aa])", size)).unwrap();
+        assert!(tree.board_size().is_err(), "SZ[{}]", size);
+        assert!(tree.check_board().is_err(), "SZ[{}]", size);
+    }
+}
@@ -0,0 +1,151 @@
+use smartgameviewer::game::GameState;
+use smartgameviewer::parser::{parse_sgf, Color};
+use smartgameviewer::pattern::{point_name, Continuation, Corner, Pattern, PatternSearch, Region};
+use smartgameviewer::playlist::PlaylistManager;
+use std::fs;
+use std::path::Path;
+
+fn game(sgf: &str) -> GameState {
+    GameState::from_tree(parse_sgf(sgf).unwrap())
+}
+
+// The top-right corner after a 4-4 stone and a white approach
+fn corner_pattern() -> Pattern {
+    let mut source = game("(;SZ[19];B[pd];W[qf])");
+    source.jump_to_end();
+    Pattern::from_board(&source.board, Region::corner(Corner::TopRight, 7, 19))
+}
+
+#[test]
+fn test_parse_region() {
+    let region = Region::parse("a19:g13", 19).unwrap();
+    assert_eq!(region, Region::corner(Corner::TopLeft, 7, 19));
+    assert_eq!((region.width(), region.height()), (7, 7));
+    assert_eq!(region.name(19), "A19:G13");
+    assert_eq!(
+        Region::parse("T1:J5", 19).unwrap(),
+        Region {
+            top: 14,
+            left: 8,
+            bottom: 18,
+            right: 18
+        }
+    );
+    assert_eq!(
+        Region::parse("bottom-right", 9).unwrap(),
+        Region::corner(Corner::BottomRight, 7, 9)
+    );
+    assert!(Region::parse("I5:A1", 19).is_err());
+    assert!(Region::parse("A20:A1", 19).is_err());
+    assert!(Region::parse("A1", 19).is_err());
+    assert_eq!(point_name((3, 15), 19), "Q16");
+}
+
+#[test]
+fn test_pattern_found_in_any_orientation_and_colours() {
+    let pattern = corner_pattern();
+    assert_eq!(pattern.stones(), 2);
+
+    let hits = pattern.find(Path::new("a.sgf"), game("(;SZ[19];B[pd];W[qf];B[nc])"));
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].move_number, 2);
+    assert_eq!(
+        hits[0].continuation,
+        Some(Continuation {
+            position: (2, 13),
+            color: Color::Black
+        })
+    );
+
+    // Mirrored into the top-left with colours swapped; the continuation comes
+    // after a move elsewhere and is reported in the pattern's frame
+    let hits = pattern.find(
+        Path::new("b.sgf"),
+        game("(;SZ[19];W[dd];B[cf];B[pp];W[fc])"),
+    );
+    assert_eq!(hits.len(), 1);
+    assert_eq!(
+        hits[0].continuation,
+        Some(Continuation {
+            position: (2, 13),
+            color: Color::Black
+        })
+    );
+
+    // An extra stone in the region, or another board size, doesn't match
+    assert!(pattern
+        .find(Path::new("c.sgf"), game("(;SZ[19];B[qc];B[pd];W[qf])"))
+        .is_empty());
+    assert!(pattern
+        .find(Path::new("d.sgf"), game("(;SZ[9];B[dd];W[ef])"))
+        .is_empty());
+}
+
+#[test]
+fn test_pattern_restored_by_a_capture() {
+    let mut source = game("(;SZ[19];B[ea])");
+    source.jump_to_end();
+    let pattern = Pattern::from_board(&source.board, Region::parse("E19:F19", 19).unwrap());
+
+    // White's F19 is captured by F18, outside the region, and Black fills in
+    let hits = pattern.find(
+        Path::new("a.sgf"),
+        game("(;SZ[19];B[ea];W[fa];B[ga];W[pp];B[fb];W[dd];B[fa])"),
+    );
+    let found: Vec<(usize, Option<Continuation>)> = hits
+        .into_iter()
+        .map(|hit| (hit.move_number, hit.continuation))
+        .collect();
+    let at_f19 = |color| {
+        Some(Continuation {
+            position: (0, 5),
+            color,
+        })
+    };
+    assert_eq!(
+        found,
+        vec![(1, at_f19(Color::White)), (5, at_f19(Color::Black))]
+    );
+}
+
+#[test]
+fn test_symmetric_pattern_counted_once() {
+    let mut source = game("(;SZ[19];B[pd])");
+    source.jump_to_end();
+    let pattern = Pattern::from_board(&source.board, Region::parse("Q16:Q16", 19).unwrap());
+    let hits = pattern.find(Path::new("a.sgf"), game("(;SZ[19];W[dp];B[pp])"));
+    let moves: Vec<usize> = hits.iter().map(|hit| hit.move_number).collect();
+    assert_eq!(moves, vec![1, 2]);
+}
+
+#[test]
+fn test_pattern_search_counts_continuations() {
+    let dir = std::env::temp_dir().join("smartgameviewer_pattern_search_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.sgf"), "(;SZ[19];B[pd];W[qf];B[nc])").unwrap();
+    fs::write(dir.join("b.sgf"), "(;SZ[19];W[dd];B[cf];B[pp];W[fc])").unwrap();
+    fs::write(dir.join("c.sgf"), "(;SZ[19];B[dp];W[fq];B[dr])").unwrap();
+    fs::write(dir.join("d.sgf"), "(;SZ[19];B[pd];W[qf])").unwrap();
+    fs::write(dir.join("e.sgf"), "(;SZ[19];B[aa])").unwrap();
+    fs::write(dir.join("f.sgf"), "not a game").unwrap();
+
+    let playlist = PlaylistManager::new(dir.to_str()).unwrap();
+    let search = PatternSearch::run(&corner_pattern(), &playlist);
+    assert_eq!(search.games(), 5);
+    assert_eq!(search.matching_games(), 4);
+    assert_eq!(search.unreadable(), [dir.join("f.sgf")]);
+
+    let black = |position| {
+        Some(Continuation {
+            position,
+            color: Color::Black,
+        })
+    };
+    assert_eq!(
+        search.continuations(),
+        vec![(black((2, 13)), 2), (black((3, 17)), 1), (None, 1)]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
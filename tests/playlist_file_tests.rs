@@ -0,0 +1,170 @@
+use smartgameviewer::playlist::PlaylistManager;
+use smartgameviewer::playlist_file::{
+    format_playlist, is_playlist_file, parse_playlist, PlaylistEntry,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_parse_playlist_entries_and_options() {
+    let text = "\
+# Study set
+pro_game.sgf
+    start = 40
+    end = 120
+    note = Watch the ladder
+
+/data/games/other.sgf
+\tnote = Absolute paths stay as they are
+";
+    let entries = parse_playlist(text, Path::new("studies")).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            PlaylistEntry {
+                path: PathBuf::from("studies/pro_game.sgf"),
+                start_move: Some(40),
+                end_move: Some(120),
+                note: Some("Watch the ladder".to_string()),
+            },
+            PlaylistEntry {
+                path: PathBuf::from("/data/games/other.sgf"),
+                note: Some("Absolute paths stay as they are".to_string()),
+                ..Default::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_playlist_errors() {
+    let base = Path::new("");
+    assert_eq!(
+        parse_playlist("  start = 3\n", base).unwrap_err(),
+        "1: option before the first game"
+    );
+    assert_eq!(
+        parse_playlist("a.sgf\n\n  start = ten\n", base).unwrap_err(),
+        "3: invalid move number: ten"
+    );
+    assert_eq!(
+        parse_playlist("a.sgf\n  speed = 2\n", base).unwrap_err(),
+        "2: unknown option: speed"
+    );
+    assert_eq!(
+        parse_playlist("a.sgf\n  start = 50\n  end = 10\n", base).unwrap_err(),
+        "3: start move 50 is after end move 10"
+    );
+}
+
+#[test]
+fn test_format_playlist_relative_paths() {
+    let base = std::env::temp_dir().join("smartgameviewer_format_playlist");
+    let mut entry = PlaylistEntry::new(base.join("2016/game4.sgf"));
+    entry.start_move = Some(70);
+    entry.note = Some("Move 78,\nthe wedge".to_string());
+    let entries = vec![
+        entry,
+        PlaylistEntry::new(base.join("../shared/game.sgf")),
+        PlaylistEntry::new(base.join("games.zip/round1/game3.sgf")),
+    ];
+
+    let text = format_playlist(&entries, &base.join("lists")).unwrap();
+    assert_eq!(
+        text,
+        "\
+# smartgameviewer playlist
+../2016/game4.sgf
+    start = 70
+    note = Move 78, the wedge
+../../shared/game.sgf
+../games.zip/round1/game3.sgf
+"
+    );
+
+    let reparsed = parse_playlist(&text, &base.join("lists")).unwrap();
+    assert_eq!(reparsed[0].path, base.join("lists/../2016/game4.sgf"));
+    assert_eq!(reparsed[0].start_move, Some(70));
+}
+
+#[test]
+fn test_is_playlist_file() {
+    assert!(is_playlist_file(Path::new("study.sgfplaylist")));
+    assert!(is_playlist_file(Path::new("STUDY.SGFPLAYLIST")));
+    assert!(!is_playlist_file(Path::new("game.sgf")));
+}
+
+#[test]
+fn test_playlist_manager_loads_playlist_file() {
+    let dir = std::env::temp_dir().join("smartgameviewer_playlist_file_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let games = fs::canonicalize("sgf").unwrap();
+    let list = dir.join("study.sgfplaylist");
+    fs::write(
+        &list,
+        format!(
+            "{0}/shodan_game.sgf\n{0}/pro_game.sgf\n    start = 10\n    end = 30\n    note = Opening\n",
+            games.display()
+        ),
+    )
+    .unwrap();
+
+    let playlist = PlaylistManager::new(list.to_str()).unwrap();
+    assert_eq!(playlist.files().len(), 2);
+    assert!(playlist.current().ends_with("shodan_game.sgf"));
+    assert_eq!(playlist.entry(0).unwrap().note, None);
+    assert_eq!(playlist.entry(1).unwrap().note.as_deref(), Some("Opening"));
+
+    // Start and end moves are applied when the game loads
+    let mut game = playlist.load(1).unwrap();
+    assert_eq!(game.current_move, 10);
+    assert_eq!(game.moves.len(), 30);
+
+    // Looping (repeat one) and Home go back to the start move, not move 0
+    game.jump_to_end();
+    assert!(game.next());
+    assert_eq!(game.current_move, 10);
+    game.jump_to_move(20);
+    game.jump_to_start();
+    assert_eq!(game.current_move, 10);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_playlist_save_round_trip() {
+    let dir = std::env::temp_dir().join("smartgameviewer_playlist_save_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("games")).unwrap();
+    for name in ["b.sgf", "a.sgf"] {
+        fs::copy("sgf/pro_game.sgf", dir.join("games").join(name)).unwrap();
+    }
+    fs::write(
+        dir.join("first.sgfplaylist"),
+        "games/b.sgf\n    end = 5\ngames/a.sgf\n",
+    )
+    .unwrap();
+
+    let playlist = PlaylistManager::new(dir.join("first.sgfplaylist").to_str()).unwrap();
+    let saved = dir.join("copy.sgfplaylist");
+    playlist.save(&saved).unwrap();
+    assert_eq!(
+        fs::read_to_string(&saved).unwrap(),
+        "# smartgameviewer playlist\ngames/b.sgf\n    end = 5\ngames/a.sgf\n"
+    );
+
+    let reloaded = PlaylistManager::new(saved.to_str()).unwrap();
+    assert_eq!(reloaded.files(), playlist.files());
+    assert_eq!(reloaded.load(0).unwrap().moves.len(), 5);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_playlist_save_rejects_stdin_games() {
+    let playlist = PlaylistManager::from_reader("(;GM[1];B[aa])".as_bytes()).unwrap();
+    let path = std::env::temp_dir().join("smartgameviewer_stdin.sgfplaylist");
+    assert!(playlist.save(&path).is_err());
+    assert!(!path.exists());
+}
@@ -1,6 +1,10 @@
-use smartgameviewer::playlist::PlaylistManager;
+use smartgameviewer::playlist::{
+    GameQuery, GameSummary, PlaylistManager, RepeatMode, ScanOptions, SortKey,
+};
+use std::path::Path;
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_single_file_playlist() {
     let playlist = PlaylistManager::new(Some("sgf/AlphaGo_LeeSedol_game4.sgf")).unwrap();
 
@@ -13,6 +17,7 @@ fn test_single_file_playlist() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_natural_sort_ordering() {
     // Create test files: game1, game10, game2, game20
     // They should sort as: game1, game2, game10, game20
@@ -60,6 +65,7 @@ fn test_natural_sort_ordering() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_folder_playlist() {
     let playlist = PlaylistManager::new(Some("test_sgf")).unwrap();
 
@@ -159,6 +165,7 @@ fn test_peek_next() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_next_returns_false_at_end() {
     let mut playlist = PlaylistManager::new(Some("test_sgf")).unwrap();
 
@@ -169,3 +176,512 @@ fn test_next_returns_false_at_end() {
     assert_eq!(playlist.next(), false);
     assert_eq!(playlist.next(), false);
 }
+
+#[test]
+fn test_jump_to() {
+    let mut playlist = PlaylistManager::new(Some("test_sgf")).unwrap();
+
+    assert_eq!(playlist.files().len(), 4);
+    assert!(playlist.jump_to(2));
+    assert_eq!(playlist.current_index(), 2);
+    assert_eq!(
+        playlist.current().file_stem().unwrap().to_string_lossy(),
+        "game10"
+    );
+
+    // Out of range leaves the position unchanged
+    assert!(!playlist.jump_to(4));
+    assert_eq!(playlist.current_index(), 2);
+}
+
+#[test]
+fn test_game_summary_metadata_and_filter() {
+    let summary = GameSummary::load(std::path::Path::new("sgf/pro_game.sgf"));
+
+    assert_eq!(summary.black.as_deref(), Some("Maruyama Toyoji"));
+    assert_eq!(summary.white.as_deref(), Some("Ito Yoji"));
+    assert_eq!(summary.date.as_deref(), Some("1976-01-28"));
+    assert_eq!(summary.result.as_deref(), Some("W+6.5"));
+    assert_eq!(summary.file_name(), "pro_game.sgf");
+
+    // Filter matches file name, players, date and result, ignoring case
+    assert!(summary.matches(""));
+    assert!(summary.matches("maruyama"));
+    assert!(summary.matches("1976"));
+    assert!(summary.matches("w+6"));
+    assert!(summary.matches("PRO_"));
+    assert!(!summary.matches("alphago"));
+}
+
+#[test]
+fn test_game_summary_unreadable_file() {
+    let summary = GameSummary::load(std::path::Path::new("/tmp/does_not_exist_xyz.sgf"));
+
+    assert_eq!(summary.black, None);
+    assert_eq!(summary.result, None);
+    assert!(summary.matches("does_not_exist"));
+}
+
+#[test]
+fn test_playlist_from_reader_collection() {
+    let input = "(;GN[First]PB[Alice];B[dd];W[pp])\n(;GN[Second]SZ[9];B[cc])";
+    let mut playlist = PlaylistManager::from_reader(input.as_bytes()).unwrap();
+
+    assert_eq!(playlist.files().len(), 2);
+    assert_eq!(playlist.current(), Path::new("<stdin>#1"));
+    let first = playlist.load_current().unwrap();
+    assert_eq!(first.get_property("GN"), Some("First"));
+    assert_eq!(first.moves.len(), 2);
+
+    let second = playlist.load(1).unwrap();
+    assert_eq!(second.board.size, 9);
+    assert!(playlist.load(2).is_err());
+
+    let summaries = playlist.summaries();
+    assert_eq!(summaries[0].black.as_deref(), Some("Alice"));
+    assert_eq!(summaries[1].file_name(), "<stdin>#2");
+
+    assert!(playlist.next());
+    assert_eq!(
+        playlist.load_current().unwrap().get_property("GN"),
+        Some("Second")
+    );
+}
+
+#[test]
+fn test_playlist_from_reader_single_game() {
+    let playlist = PlaylistManager::from_reader("(;GN[Only];B[dd])".as_bytes()).unwrap();
+    assert!(playlist.is_single_file());
+    assert_eq!(playlist.current(), Path::new("<stdin>"));
+
+    assert!(PlaylistManager::from_reader("not sgf".as_bytes()).is_err());
+}
+
+// games/2023/round1/..., games/2023/round10/..., games/drafts/..., games/top.sgf
+fn make_game_tree(name: &str) -> std::path::PathBuf {
+    let root = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&root);
+    for file in [
+        "top.sgf",
+        "2023/round10/game1.sgf",
+        "2023/round2/game10.sgf",
+        "2023/round2/game9.gib",
+        "2023/round2/notes.txt",
+        "drafts/wip.sgf",
+    ] {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "(;GM[1];B[dd])").unwrap();
+    }
+    root
+}
+
+fn relative_files(playlist: &PlaylistManager, root: &Path) -> Vec<String> {
+    playlist
+        .files()
+        .iter()
+        .map(|path| {
+            path.strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_recursive_scan_natural_order() {
+    let root = make_game_tree("smartgameviewer_recursive_test");
+    let playlist = PlaylistManager::new(root.to_str()).unwrap();
+
+    assert_eq!(
+        relative_files(&playlist, &root),
+        vec![
+            "drafts/wip.sgf",
+            "top.sgf",
+            "2023/round2/game9.gib",
+            "2023/round2/game10.sgf",
+            "2023/round10/game1.sgf",
+        ]
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_name_without_number_sorts_first() {
+    let root = std::env::temp_dir().join("smartgameviewer_flat_order_test");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    for name in ["game10.sgf", "game2.sgf", "game.sgf", "game1.sgf"] {
+        std::fs::write(root.join(name), "(;GM[1])").unwrap();
+    }
+
+    let mut playlist = PlaylistManager::new(root.to_str()).unwrap();
+    let expected = vec!["game.sgf", "game1.sgf", "game2.sgf", "game10.sgf"];
+    assert_eq!(relative_files(&playlist, &root), expected);
+    playlist.sort(SortKey::Name);
+    assert_eq!(relative_files(&playlist, &root), expected);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_scan_depth_and_patterns() {
+    let root = make_game_tree("smartgameviewer_scan_options_test");
+    let scan = |options: ScanOptions| {
+        let playlist = PlaylistManager::with_options(root.to_str(), &options).unwrap();
+        relative_files(&playlist, &root)
+    };
+
+    let shallow = ScanOptions {
+        max_depth: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(scan(shallow), vec!["top.sgf"]);
+
+    let one_level = ScanOptions {
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(scan(one_level), vec!["drafts/wip.sgf", "top.sgf"]);
+
+    // Folder exclusion prunes the whole subtree; name-only patterns match at any depth
+    let filtered = ScanOptions {
+        include: vec!["*.sgf".to_string()],
+        exclude: vec!["drafts".to_string(), "2023/round1*".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(scan(filtered), vec!["top.sgf", "2023/round2/game10.sgf"]);
+
+    let by_path = ScanOptions {
+        include: vec!["2023/**/game1*".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        scan(by_path),
+        vec!["2023/round2/game10.sgf", "2023/round10/game1.sgf"]
+    );
+
+    assert!(ScanOptions {
+        include: vec!["[".to_string()],
+        ..Default::default()
+    }
+    .validate()
+    .is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_symlinks() {
+    let root = make_game_tree("smartgameviewer_symlink_test");
+    let outside = make_game_tree("smartgameviewer_symlink_target");
+    // A link back to the root must not loop; a link elsewhere is followed
+    std::os::unix::fs::symlink(&root, root.join("drafts/loop")).unwrap();
+    std::os::unix::fs::symlink(outside.join("drafts"), root.join("linked")).unwrap();
+
+    let followed = PlaylistManager::new(root.to_str()).unwrap();
+    assert_eq!(followed.files().len(), 6);
+    assert!(followed.files().contains(&root.join("linked/wip.sgf")));
+
+    let options = ScanOptions {
+        follow_symlinks: false,
+        ..Default::default()
+    };
+    let not_followed = PlaylistManager::with_options(root.to_str(), &options).unwrap();
+    assert_eq!(not_followed.files().len(), 5);
+
+    std::fs::remove_dir_all(&root).unwrap();
+    std::fs::remove_dir_all(&outside).unwrap();
+}
+
+fn file_names(playlist: &PlaylistManager) -> Vec<String> {
+    playlist
+        .files()
+        .iter()
+        .map(|path| path.file_stem().unwrap().to_string_lossy().to_string())
+        .collect()
+}
+
+#[test]
+fn test_filter_by_metadata() {
+    let filtered = |query: GameQuery| {
+        let mut playlist = PlaylistManager::new(Some("sgf")).unwrap();
+        playlist.filter(&query);
+        file_names(&playlist)
+    };
+
+    let lee_sedol = GameQuery {
+        player: Some("lee sedol".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        filtered(lee_sedol),
+        vec!["AlphaGo_LeeSedol_game4", "AlphaGo_LeeSedol_game5"]
+    );
+
+    let resignations_in_2016 = GameQuery {
+        date: Some("2016".to_string()),
+        result: Some("+R".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(filtered(resignations_in_2016).len(), 2);
+
+    let kisei = GameQuery {
+        event: Some("Kisei".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(filtered(kisei), vec!["pro_game"]);
+
+    let range = GameQuery {
+        date: Some("1970..2000".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(filtered(range), vec!["pro_game", "shodan_game"]);
+
+    let open_ended = GameQuery {
+        date: Some("2000-06..".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(filtered(open_ended).len(), 2);
+
+    let mut playlist = PlaylistManager::new(Some("sgf")).unwrap();
+    let white_wins = GameQuery {
+        result: Some("w+".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(playlist.filter(&white_wins), 3);
+    assert_eq!(playlist.current_index(), 0);
+    assert!(GameQuery::default().is_empty());
+}
+
+#[test]
+fn test_sort_by_metadata() {
+    let mut playlist = PlaylistManager::new(Some("sgf")).unwrap();
+
+    playlist.sort(SortKey::Date);
+    assert_eq!(
+        file_names(&playlist),
+        vec![
+            "pro_game",
+            "shodan_game",
+            "AlphaGo_LeeSedol_game4",
+            "AlphaGo_LeeSedol_game5"
+        ]
+    );
+
+    playlist.sort(SortKey::Player);
+    assert_eq!(
+        file_names(&playlist),
+        vec![
+            "AlphaGo_LeeSedol_game4",
+            "AlphaGo_LeeSedol_game5",
+            "pro_game",
+            "shodan_game"
+        ]
+    );
+
+    playlist.sort(SortKey::Event);
+    assert_eq!(
+        file_names(&playlist),
+        vec![
+            "pro_game",
+            "AlphaGo_LeeSedol_game4",
+            "AlphaGo_LeeSedol_game5",
+            "shodan_game"
+        ]
+    );
+
+    playlist.sort(SortKey::Name);
+    assert_eq!(file_names(&playlist)[0], "AlphaGo_LeeSedol_game4");
+
+    assert_eq!(SortKey::from_name("DATE"), Some(SortKey::Date));
+    assert_eq!(SortKey::from_name("rating"), None);
+}
+
+#[test]
+fn test_sort_missing_metadata_last() {
+    let dir = std::env::temp_dir().join("smartgameviewer_sort_missing_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.sgf"), "(;GM[1];B[dd])").unwrap();
+    std::fs::write(dir.join("b.sgf"), "(;DT[2020-05-01];B[dd])").unwrap();
+
+    let mut playlist = PlaylistManager::new(dir.to_str()).unwrap();
+    playlist.sort(SortKey::Date);
+    assert_eq!(file_names(&playlist), vec!["b", "a"]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_previous_and_wrap_around() {
+    let mut playlist = PlaylistManager::new(Some("test_sgf")).unwrap();
+    assert!(!playlist.has_previous());
+    assert!(!playlist.previous());
+
+    // Repeat all (the default) wraps in both directions
+    assert_eq!(playlist.repeat(), RepeatMode::All);
+    assert_eq!(playlist.previous_index(), Some(3));
+    playlist.jump_to(3);
+    assert_eq!(playlist.next_index(), Some(0));
+    assert_eq!(playlist.index_after_end(), Some(0));
+
+    assert!(playlist.previous());
+    assert_eq!(playlist.current_index(), 2);
+
+    playlist.set_repeat(RepeatMode::Off);
+    playlist.jump_to(3);
+    assert_eq!(playlist.next_index(), None);
+    assert_eq!(playlist.index_after_end(), None);
+    playlist.jump_to(0);
+    assert_eq!(playlist.previous_index(), None);
+    assert_eq!(playlist.index_after_end(), Some(1));
+
+    // Repeat one stays on the game when it ends but still allows skipping
+    playlist.set_repeat(RepeatMode::One);
+    assert_eq!(playlist.index_after_end(), None);
+    assert_eq!(playlist.next_index(), Some(1));
+}
+
+#[test]
+fn test_repeat_single_file_stays_on_game() {
+    let playlist = PlaylistManager::new(Some("sgf/pro_game.sgf")).unwrap();
+    assert_eq!(playlist.next_index(), None);
+    assert_eq!(playlist.previous_index(), None);
+    assert_eq!(playlist.index_after_end(), None);
+}
+
+#[test]
+fn test_repeat_mode_names() {
+    assert_eq!(RepeatMode::from_name("ONE"), Some(RepeatMode::One));
+    assert_eq!(RepeatMode::from_name("none"), Some(RepeatMode::Off));
+    assert_eq!(RepeatMode::from_name("sometimes"), None);
+    assert_eq!(RepeatMode::All.cycle(), RepeatMode::One);
+    assert_eq!(RepeatMode::One.cycle(), RepeatMode::Off);
+    assert_eq!(RepeatMode::Off.cycle(), RepeatMode::All);
+}
+
+#[test]
+fn test_shuffle_is_reproducible() {
+    let dir = make_game_tree("smartgameviewer_shuffle_test");
+    let original = file_names(&PlaylistManager::new(dir.to_str()).unwrap());
+
+    let mut first = PlaylistManager::new(dir.to_str()).unwrap();
+    let mut second = PlaylistManager::new(dir.to_str()).unwrap();
+    first.shuffle(42);
+    second.shuffle(42);
+    assert_eq!(first.shuffle_seed(), Some(42));
+    assert_eq!(file_names(&first), file_names(&second));
+    assert_ne!(file_names(&first), original);
+
+    let mut sorted = file_names(&first);
+    sorted.sort();
+    let mut expected = original.clone();
+    expected.sort();
+    assert_eq!(sorted, expected);
+
+    // Reshuffling starts from the original order, not the shuffled one
+    second.shuffle(7);
+    second.shuffle(42);
+    assert_eq!(file_names(&first), file_names(&second));
+
+    first.unshuffle();
+    assert_eq!(first.shuffle_seed(), None);
+    assert_eq!(file_names(&first), original);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_shuffle_keeps_current_game() {
+    let mut playlist = PlaylistManager::new(Some("test_sgf")).unwrap();
+    playlist.jump_to(2);
+    let current = playlist.current().to_path_buf();
+
+    playlist.shuffle(1);
+    assert_eq!(playlist.current(), current);
+    playlist.unshuffle();
+    assert_eq!(playlist.current(), current);
+    assert_eq!(playlist.current_index(), 2);
+}
+
+#[test]
+fn test_refresh_picks_up_new_and_deleted_files() {
+    let root = make_game_tree("smartgameviewer_refresh_test");
+    let mut playlist = PlaylistManager::new(root.to_str()).unwrap();
+    assert!(playlist.is_watchable());
+    assert!(playlist.refresh().unwrap().is_empty());
+
+    // top.sgf is playing; a new round2 game lands in natural order
+    playlist.jump_to(1);
+    std::fs::write(root.join("2023/round2/game11.sgf"), "(;GM[1])").unwrap();
+    std::fs::remove_file(root.join("drafts/wip.sgf")).unwrap();
+    let changes = playlist.refresh().unwrap();
+    assert_eq!(changes.added, vec![root.join("2023/round2/game11.sgf")]);
+    assert_eq!(changes.removed, vec![root.join("drafts/wip.sgf")]);
+    assert_eq!(
+        relative_files(&playlist, &root),
+        vec![
+            "top.sgf",
+            "2023/round2/game9.gib",
+            "2023/round2/game10.sgf",
+            "2023/round2/game11.sgf",
+            "2023/round10/game1.sgf",
+        ]
+    );
+    assert!(playlist.current().ends_with("top.sgf"));
+
+    // The game on screen stays until playback moves on
+    std::fs::remove_file(root.join("top.sgf")).unwrap();
+    assert!(playlist.refresh().unwrap().is_empty());
+    assert_eq!(playlist.files().len(), 5);
+    playlist.jump_to(1);
+    assert_eq!(
+        playlist.refresh().unwrap().removed,
+        vec![root.join("top.sgf")]
+    );
+    assert_eq!(playlist.files().len(), 4);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_refresh_keeps_order_and_filter() {
+    let root = std::env::temp_dir().join("smartgameviewer_refresh_filter_test");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    for (name, date) in [("a.sgf", "2020"), ("b.sgf", "2010"), ("c.sgf", "2015")] {
+        std::fs::write(root.join(name), format!("(;GM[1]DT[{}]PB[Lee])", date)).unwrap();
+    }
+
+    let mut playlist = PlaylistManager::new(root.to_str()).unwrap();
+    playlist.filter(&GameQuery {
+        player: Some("lee".to_string()),
+        ..Default::default()
+    });
+    playlist.sort(SortKey::Date);
+    assert_eq!(file_names(&playlist), vec!["b", "c", "a"]);
+    playlist.jump_to(1);
+
+    // New games go where the date sort puts them, not next to their neighbour
+    // by name; games failing the filter stay out
+    std::fs::write(root.join("bb.sgf"), "(;GM[1]DT[2021]PB[Lee])").unwrap();
+    std::fs::write(root.join("d.sgf"), "(;GM[1]DT[2021]PB[Cho])").unwrap();
+    std::fs::write(root.join("e.sgf"), "(;GM[1]DT[2005]PB[Lee])").unwrap();
+    let changes = playlist.refresh().unwrap();
+    assert_eq!(changes.added, vec![root.join("bb.sgf"), root.join("e.sgf")]);
+    assert_eq!(file_names(&playlist), vec!["e", "b", "c", "a", "bb"]);
+    assert!(playlist.current().ends_with("c.sgf"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_refresh_without_folder() {
+    let mut playlist = PlaylistManager::new(Some("sgf/pro_game.sgf")).unwrap();
+    assert!(!playlist.is_watchable());
+    assert!(playlist.refresh().unwrap().is_empty());
+}
@@ -0,0 +1,151 @@
+use smartgameviewer::game::{Board, GameState};
+use smartgameviewer::parser::{parse_sgf, Color};
+use smartgameviewer::playlist::PlaylistManager;
+use smartgameviewer::position::{transform, PositionHash, PositionIndex, SYMMETRIES};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+fn game_at(sgf: &str, move_number: usize) -> GameState {
+    let mut game = GameState::from_tree(parse_sgf(sgf).unwrap());
+    game.jump_to_move(move_number);
+    game
+}
+
+#[test]
+fn test_symmetries_are_distinct() {
+    let images: HashSet<(u8, u8)> = (0..SYMMETRIES)
+        .map(|symmetry| transform(symmetry, 19, (2, 5)))
+        .collect();
+    assert_eq!(images.len(), 8);
+    assert_eq!(transform(0, 19, (2, 5)), (2, 5));
+    assert_eq!(transform(3, 19, (2, 5)), (16, 13));
+}
+
+#[test]
+fn test_hash_follows_the_board() {
+    let mut board = Board::new(19);
+    let empty = board.hash().clone();
+    board.set(3, 3, Color::Black);
+    board.set(15, 15, Color::White);
+    assert_ne!(board.hash(), &empty);
+
+    // Overwriting and clearing take the old stone out of the hash
+    board.set(15, 15, Color::Black);
+    board.clear(15, 15);
+    board.clear(3, 3);
+    assert_eq!(board.hash(), &empty);
+    assert_ne!(Board::new(9).hash(), &empty);
+}
+
+#[test]
+fn test_hash_matches_transpositions_symmetry_and_colours() {
+    let sgf = "(;SZ[19];B[dd];W[pp];B[dp])";
+    let position = game_at(sgf, 3);
+    let transposed = game_at("(;SZ[19];B[dp];W[pp];B[dd])", 3);
+    assert_eq!(position.position_hash(), transposed.position_hash());
+
+    // Mirrored left to right
+    let mirrored = game_at("(;SZ[19];B[pd];W[dp];B[pp])", 3);
+    assert_ne!(
+        position.position_hash().exact(),
+        mirrored.position_hash().exact()
+    );
+    assert_eq!(
+        position.position_hash().canonical(),
+        mirrored.position_hash().canonical()
+    );
+
+    // Colours swapped
+    let swapped = game_at("(;SZ[19];W[dd];B[pp];W[dp])", 3);
+    assert_eq!(
+        position.position_hash().canonical(),
+        swapped.position_hash().canonical()
+    );
+
+    // Stepping back restores the earlier hash
+    let mut game = game_at(sgf, 3);
+    game.previous();
+    assert_eq!(game.position_hash(), game_at(sgf, 2).position_hash());
+    assert_ne!(
+        game.position_hash().canonical(),
+        PositionHash::empty(19).canonical()
+    );
+}
+
+#[test]
+fn test_position_index_finds_games() {
+    let dir = std::env::temp_dir().join("smartgameviewer_position_index_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.sgf"), "(;SZ[19];B[dd];W[pp];B[dp];W[pd])").unwrap();
+    fs::write(dir.join("b.sgf"), "(;SZ[19];B[pd];W[dp];B[pp];W[qq])").unwrap();
+    fs::write(dir.join("c.sgf"), "(;SZ[19];B[qq];W[dd])").unwrap();
+    fs::write(dir.join("d.sgf"), "not a game").unwrap();
+
+    let playlist = PlaylistManager::new(dir.to_str()).unwrap();
+    let index = PositionIndex::build(&playlist);
+    assert_eq!(index.games().len(), 3);
+    assert_eq!(index.unreadable(), [dir.join("d.sgf")]);
+
+    let query = game_at("(;SZ[19];B[dp];W[pp];B[dd])", 3);
+    let hits = index.find(query.position_hash());
+    let found: Vec<(&Path, usize, bool)> = hits
+        .iter()
+        .map(|hit| {
+            (
+                hit.path.strip_prefix(&dir).unwrap(),
+                hit.move_number,
+                hit.exact,
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Path::new("a.sgf"), 3, true),
+            (Path::new("b.sgf"), 3, false)
+        ]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_position_reached_through_a_capture() {
+    // Black captures the corner stone; the other game passes instead
+    let captured = game_at("(;SZ[9];B[ba];W[aa];B[ab])", 3);
+    let passed = game_at("(;SZ[9];B[ba];W[];B[ab])", 3);
+    assert_eq!(captured.position_hash(), passed.position_hash());
+
+    let mut index = PositionIndex::default();
+    index.add_game(
+        Path::new("passed.sgf"),
+        game_at("(;SZ[9];B[ba];W[];B[ab])", 0),
+    );
+    let hits = index.find(captured.position_hash());
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].move_number, 3);
+    assert!(hits[0].exact);
+}
+
+#[test]
+fn test_position_index_skips_off_board_games() {
+    let dir = std::env::temp_dir().join("smartgameviewer_position_off_board_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.sgf"), "(;SZ[9];B[cc];W[gg])").unwrap();
+    // "ss" is on a 19x19 board but not on this 9x9 one
+    fs::write(dir.join("b.sgf"), "(;SZ[9];B[aa];W[ss])").unwrap();
+    fs::write(dir.join("c.sgf"), "(;SZ[0];B[aa])").unwrap();
+
+    let playlist = PlaylistManager::new(dir.to_str()).unwrap();
+    let index = PositionIndex::build(&playlist);
+    assert_eq!(index.games().len(), 1);
+    assert_eq!(index.unreadable(), [dir.join("b.sgf"), dir.join("c.sgf")]);
+
+    let query = game_at("(;SZ[9];B[cc];W[gg])", 2);
+    assert_eq!(index.find(query.position_hash()).len(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,71 @@
+use smartgameviewer::resume::ViewerState;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_state_round_trip() {
+    let dir = std::env::temp_dir().join("smartgameviewer_state_test");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("nested/state");
+
+    let mut state = ViewerState::default();
+    state.set_last_move(Path::new("sgf/pro_game.sgf"), 42);
+    state.set_last_move(Path::new("sgf/shodan_game.sgf"), 7);
+    state.set_last_game(Path::new("sgf"), Path::new("sgf/pro_game.sgf"));
+    state.save(&path).unwrap();
+
+    let loaded = ViewerState::load(&path);
+    assert_eq!(loaded, state);
+    assert_eq!(loaded.last_move(Path::new("sgf/pro_game.sgf")), Some(42));
+    // Paths are compared absolute, wherever the viewer is started from
+    let absolute = std::env::current_dir().unwrap().join("sgf/pro_game.sgf");
+    assert_eq!(loaded.last_move(&absolute), Some(42));
+    assert_eq!(loaded.last_game(Path::new("sgf")), Some(absolute.as_path()));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_move_zero_clears_entry() {
+    let mut state = ViewerState::default();
+    state.set_last_move(Path::new("game.sgf"), 10);
+    state.set_last_move(Path::new("game.sgf"), 0);
+    assert_eq!(state.last_move(Path::new("game.sgf")), None);
+}
+
+#[test]
+fn test_load_missing_or_damaged_state() {
+    let missing = std::env::temp_dir().join("smartgameviewer_no_such_state");
+    assert_eq!(ViewerState::load(&missing), ViewerState::default());
+
+    let path = std::env::temp_dir().join("smartgameviewer_damaged_state");
+    fs::write(
+        &path,
+        "# smartgameviewer state\nmove\t/games/a.sgf\tlots\nmove\t/games/b.sgf\t12\ngarbage\n",
+    )
+    .unwrap();
+    let state = ViewerState::load(&path);
+    assert_eq!(state.last_move(Path::new("/games/a.sgf")), None);
+    assert_eq!(state.last_move(Path::new("/games/b.sgf")), Some(12));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_resume_point() {
+    let files: Vec<PathBuf> = ["sgf/a.sgf", "sgf/b.sgf", "sgf/c.sgf"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    let mut state = ViewerState::default();
+    assert_eq!(state.resume_point(Path::new("sgf"), &files), None);
+
+    state.set_last_game(Path::new("sgf"), Path::new("sgf/b.sgf"));
+    assert_eq!(state.resume_point(Path::new("sgf"), &files), Some((1, 0)));
+    state.set_last_move(Path::new("sgf/b.sgf"), 33);
+    assert_eq!(state.resume_point(Path::new("sgf"), &files), Some((1, 33)));
+
+    // The game is no longer in the playlist
+    assert_eq!(state.resume_point(Path::new("sgf"), &files[..1]), None);
+    // Another playlist has its own position
+    assert_eq!(state.resume_point(Path::new("other"), &files), None);
+}
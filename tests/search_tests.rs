@@ -0,0 +1,65 @@
+use smartgameviewer::game::GameState;
+use smartgameviewer::parser::parse_sgf;
+use smartgameviewer::search::{find_matches, GameSearch, SearchField};
+
+fn game(sgf: &str) -> GameState {
+    GameState::from_tree(parse_sgf(sgf).unwrap())
+}
+
+#[test]
+fn test_find_matches_ignores_case() {
+    assert_eq!(
+        find_matches("Ladder, then a LADDER", "ladder"),
+        vec![0..6, 15..21]
+    );
+    assert_eq!(find_matches("aaaa", "aa"), vec![0..2, 2..4]);
+    assert_eq!(find_matches("Ünterschied", "ün"), vec![0..3]);
+    assert!(find_matches("anything", "").is_empty());
+    assert!(find_matches("short", "shorter").is_empty());
+}
+
+#[test]
+fn test_search_comments_names_and_labels() {
+    let game = game(
+        "(;GM[1]C[A game about the ladder];B[dd];W[pp]C[No ladder yet]\
+         ;B[dp]N[Ladder breaker];W[pd]LB[cc:ladder][dd:A];B[qq])",
+    );
+    let search = GameSearch::new(&game, " Ladder ");
+    assert_eq!(search.query(), "Ladder");
+
+    let moves: Vec<usize> = search.matches().iter().map(|m| m.move_number).collect();
+    assert_eq!(moves, vec![0, 2, 3, 4]);
+    assert_eq!(search.at(0).unwrap().fields[0].field, SearchField::Comment);
+    assert_eq!(search.at(3).unwrap().fields[0].field, SearchField::Name);
+    let label = &search.at(4).unwrap().fields[0];
+    assert_eq!(label.field, SearchField::Label);
+    assert_eq!(label.text, "ladder");
+    assert_eq!(search.at(1), None);
+    assert_eq!(search.position(3), Some(2));
+}
+
+#[test]
+fn test_search_cycles_between_matches() {
+    let game = game("(;GM[1];B[dd]C[ko];W[pp];B[dp]C[Ko fight];W[pd];B[qq]C[kos])");
+    let search = GameSearch::new(&game, "ko");
+    assert_eq!(search.next_after(0), Some(1));
+    assert_eq!(search.next_after(1), Some(3));
+    assert_eq!(search.next_after(5), Some(1));
+    assert_eq!(search.previous_before(3), Some(1));
+    assert_eq!(search.previous_before(1), Some(5));
+
+    let none = GameSearch::new(&game, "seki");
+    assert!(none.matches().is_empty());
+    assert_eq!(none.next_after(0), None);
+    assert_eq!(none.previous_before(0), None);
+}
+
+#[test]
+fn test_search_comments_on_setup_nodes() {
+    let game =
+        game("(;GM[1];AB[dd]C[Handicap stones];B[pp];AW[qq]C[White resets the corner];W[dp])");
+    let search = GameSearch::new(&game, "handicap");
+    assert_eq!(search.next_after(0), Some(0));
+    let search = GameSearch::new(&game, "corner");
+    assert_eq!(search.next_after(0), Some(1));
+}
@@ -10,17 +10,17 @@ fn test_board_state_after_moves() {
         Move {
             color: Color::Black,
             position: Some((3, 3)),
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::White,
             position: Some((15, 3)),
-            comment: None,
+            ..Default::default()
         },
         Move {
             color: Color::Black,
             position: Some((3, 15)),
-            comment: None,
+            ..Default::default()
         },
     ];
 